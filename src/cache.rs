@@ -0,0 +1,507 @@
+//! Response cache for gh calls with ETag revalidation
+
+use crate::config::CacheConfig;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// `gh api` options that consume the following argument
+const API_VALUE_FLAGS: &[&str] = &[
+    "-X",
+    "--method",
+    "-H",
+    "--header",
+    "-f",
+    "--raw-field",
+    "-F",
+    "--field",
+    "--input",
+    "-q",
+    "--jq",
+    "-t",
+    "--template",
+    "-p",
+    "--preview",
+    "--hostname",
+    "--cache",
+];
+
+/// `gh api` options that send a request body (and default the method to POST)
+const API_BODY_FLAGS: &[&str] = &["-f", "--raw-field", "-F", "--field", "--input"];
+
+/// `gh api` options that change the output format, which rules out `--include` parsing
+const API_TRANSFORM_FLAGS: &[&str] = &[
+    "-q",
+    "--jq",
+    "-t",
+    "--template",
+    "--paginate",
+    "--slurp",
+    "-i",
+    "--include",
+];
+
+/// Subcommand verbs of porcelain gh commands that only read state
+const READ_VERBS: &[&str] = &["view", "list", "diff", "checks", "status"];
+
+/// How a gh invocation interacts with the cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CallKind {
+    /// Read-only call whose output can be cached
+    Read,
+    /// Call that changes state on GitHub; invalidates related entries
+    Mutation,
+    /// Call that is neither cached nor invalidating (e.g. downloads, GraphQL)
+    Passthrough,
+}
+
+/// Cache-relevant facts about a gh invocation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallInfo {
+    pub kind: CallKind,
+
+    /// Repository the call targets, as lowercase "owner/repo"
+    pub scope: Option<String>,
+
+    /// Resource type used to select a TTL (e.g. "pulls", "contents")
+    pub resource: String,
+
+    /// Whether this is a plain `gh api` GET that can be revalidated with If-None-Match
+    pub conditional: bool,
+}
+
+impl CallInfo {
+    /// Classify a gh argument list
+    pub fn classify(args: &[&str]) -> Self {
+        match args.split_first() {
+            Some((&"api", rest)) => Self::classify_api(rest),
+            Some(_) => Self::classify_command(args),
+            None => Self::passthrough(String::new()),
+        }
+    }
+
+    fn passthrough(resource: String) -> Self {
+        Self {
+            kind: CallKind::Passthrough,
+            scope: None,
+            resource,
+            conditional: false,
+        }
+    }
+
+    fn classify_api(args: &[&str]) -> Self {
+        let mut method = None;
+        let mut has_body = false;
+        let mut plain = true;
+        let mut endpoint = None;
+
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            if API_TRANSFORM_FLAGS.contains(arg) {
+                plain = false;
+            }
+            if API_VALUE_FLAGS.contains(arg) {
+                let value = iter.next();
+                if matches!(*arg, "-X" | "--method") {
+                    method = value.map(|m| m.to_ascii_uppercase());
+                }
+                if API_BODY_FLAGS.contains(arg) {
+                    has_body = true;
+                }
+            } else if !arg.starts_with('-') && endpoint.is_none() {
+                endpoint = Some(*arg);
+            }
+        }
+
+        let path = endpoint
+            .unwrap_or_default()
+            .split('?')
+            .next()
+            .unwrap_or_default()
+            .trim_start_matches('/');
+
+        if path == "graphql" {
+            return Self::passthrough("graphql".to_string());
+        }
+
+        let segments: Vec<&str> = path.split('/').collect();
+        let (scope, resource) = match segments.as_slice() {
+            ["repos", owner, repo, rest @ ..] => (
+                Some(format!("{owner}/{repo}").to_ascii_lowercase()),
+                rest.first().copied().unwrap_or("repo").to_string(),
+            ),
+            [first, ..] => (None, first.to_string()),
+            [] => (None, String::new()),
+        };
+
+        let method = method.unwrap_or_else(|| if has_body { "POST" } else { "GET" }.to_string());
+        let kind = if method == "GET" { CallKind::Read } else { CallKind::Mutation };
+
+        Self {
+            kind,
+            scope,
+            resource,
+            conditional: kind == CallKind::Read && plain,
+        }
+    }
+
+    fn classify_command(args: &[&str]) -> Self {
+        let command = args[0];
+        let verb = args.get(1).copied().unwrap_or_default();
+
+        let resource = match command {
+            "pr" => "pulls",
+            "repo" => "repo",
+            "release" => "releases",
+            "run" => "actions",
+            "issue" => "issues",
+            other => other,
+        }
+        .to_string();
+
+        let kind = if command == "search" || READ_VERBS.contains(&verb) {
+            CallKind::Read
+        } else if verb == "download" {
+            CallKind::Passthrough
+        } else {
+            CallKind::Mutation
+        };
+
+        Self {
+            kind,
+            scope: command_scope(args),
+            resource,
+            conditional: false,
+        }
+    }
+}
+
+/// Find the "owner/repo" a porcelain gh command targets
+fn command_scope(args: &[&str]) -> Option<String> {
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if matches!(*arg, "--repo" | "-R") {
+            return iter.next().map(|r| r.to_ascii_lowercase());
+        }
+        if let Some(repo) = arg.strip_prefix("--repo=") {
+            return Some(repo.to_ascii_lowercase());
+        }
+    }
+
+    // `gh repo <verb> owner/repo`
+    match args {
+        ["repo", _, target, ..] if target.contains('/') && !target.starts_with('-') => {
+            Some(target.to_ascii_lowercase())
+        }
+        _ => None,
+    }
+}
+
+/// A cached gh response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheEntry {
+    /// Raw stdout of the gh call (response body for `gh api`)
+    pub body: String,
+
+    /// ETag returned by GitHub, if any
+    pub etag: Option<String>,
+
+    /// Repository the entry belongs to, used for invalidation
+    pub scope: Option<String>,
+
+    /// Unix timestamp (seconds) when the entry was stored or last revalidated
+    pub stored_at: u64,
+}
+
+/// Result of a cache lookup
+#[derive(Debug)]
+pub enum Lookup {
+    /// Entry is within its TTL and can be returned as-is
+    Fresh(String),
+    /// Entry exists but must be revalidated
+    Stale(CacheEntry),
+    /// No entry
+    Miss,
+}
+
+/// In-memory (optionally on-disk) cache of gh responses keyed by account + arguments
+#[derive(Debug)]
+pub struct ResponseCache {
+    config: CacheConfig,
+    entries: Mutex<HashMap<String, CacheEntry>>,
+}
+
+impl ResponseCache {
+    /// Create a new cache from configuration
+    pub fn new(config: CacheConfig) -> Self {
+        Self {
+            config,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Whether caching is enabled
+    pub fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Build the cache key for an account and gh argument list
+    pub fn key(account: &str, args: &[&str]) -> String {
+        format!("{account}\u{0}{}", args.join("\u{1f}"))
+    }
+
+    /// TTL for a resource type
+    pub fn ttl(&self, resource: &str) -> Duration {
+        let secs = self
+            .config
+            .ttl_secs
+            .get(resource)
+            .copied()
+            .unwrap_or(self.config.default_ttl_secs);
+        Duration::from_secs(secs)
+    }
+
+    /// Look up an entry, falling back to the on-disk cache if configured
+    pub fn lookup(&self, key: &str, info: &CallInfo) -> Lookup {
+        let entry = self.entries.lock().unwrap().get(key).cloned();
+        let entry = match entry.or_else(|| self.load(key, info.scope.as_deref())) {
+            Some(entry) => entry,
+            None => return Lookup::Miss,
+        };
+
+        let age = now_secs().saturating_sub(entry.stored_at);
+        if age < self.ttl(&info.resource).as_secs() {
+            Lookup::Fresh(entry.body)
+        } else {
+            Lookup::Stale(entry)
+        }
+    }
+
+    /// Store a response
+    pub fn store(&self, key: &str, info: &CallInfo, body: &str, etag: Option<String>) {
+        let entry = CacheEntry {
+            body: body.to_string(),
+            etag,
+            scope: info.scope.clone(),
+            stored_at: now_secs(),
+        };
+        self.persist(key, &entry);
+        self.entries.lock().unwrap().insert(key.to_string(), entry);
+    }
+
+    /// Mark an entry as revalidated (e.g. after a 304 Not Modified)
+    pub fn touch(&self, key: &str, mut entry: CacheEntry) -> String {
+        entry.stored_at = now_secs();
+        self.persist(key, &entry);
+        let body = entry.body.clone();
+        self.entries.lock().unwrap().insert(key.to_string(), entry);
+        body
+    }
+
+    /// Drop entries affected by a mutation.
+    ///
+    /// A scoped mutation drops that repository's entries plus unscoped entries
+    /// (searches, repo listings) that may include it; an unscoped mutation clears everything.
+    pub fn invalidate(&self, scope: Option<&str>) {
+        let mut entries = self.entries.lock().unwrap();
+        let before = entries.len();
+        match scope {
+            Some(scope) => entries.retain(|_, e| e.scope.as_deref().is_some_and(|s| s != scope)),
+            None => entries.clear(),
+        }
+        tracing::debug!(
+            "Cache invalidated {} entries for scope {:?}",
+            before - entries.len(),
+            scope.unwrap_or("*")
+        );
+        drop(entries);
+
+        if let Some(dir) = self.dir() {
+            let dirs = match scope {
+                Some(scope) => vec![dir.join(scope_dir(Some(scope))), dir.join(scope_dir(None))],
+                None => vec![dir],
+            };
+            for dir in dirs {
+                if dir.exists()
+                    && let Err(e) = fs::remove_dir_all(&dir)
+                {
+                    tracing::warn!("Failed to remove cache dir {}: {}", dir.display(), e);
+                }
+            }
+        }
+    }
+
+    fn dir(&self) -> Option<PathBuf> {
+        self.config
+            .dir
+            .as_ref()
+            .map(|d| PathBuf::from(shellexpand::tilde(d).as_ref()))
+    }
+
+    fn entry_path(&self, key: &str, scope: Option<&str>) -> Option<PathBuf> {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        self.dir()
+            .map(|dir| dir.join(scope_dir(scope)).join(format!("{:016x}.json", hasher.finish())))
+    }
+
+    fn load(&self, key: &str, scope: Option<&str>) -> Option<CacheEntry> {
+        let path = self.entry_path(key, scope)?;
+        let content = fs::read_to_string(path).ok()?;
+        let entry: CacheEntry = serde_json::from_str(&content).ok()?;
+        self.entries.lock().unwrap().insert(key.to_string(), entry.clone());
+        Some(entry)
+    }
+
+    fn persist(&self, key: &str, entry: &CacheEntry) {
+        let Some(path) = self.entry_path(key, entry.scope.as_deref()) else {
+            return;
+        };
+        let result = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(&path, serde_json::to_vec(entry)?));
+        if let Err(e) = result {
+            tracing::warn!("Failed to write cache entry {}: {}", path.display(), e);
+        }
+    }
+}
+
+/// Directory name for a scope in the on-disk cache
+fn scope_dir(scope: Option<&str>) -> String {
+    scope.map_or_else(|| "_global".to_string(), |s| s.replace('/', "__"))
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn info(args: &[&str]) -> CallInfo {
+        CallInfo::classify(args)
+    }
+
+    #[test]
+    fn test_classify_api_get() {
+        let info = info(&["api", "repos/ScottIdler/gx/pulls/1"]);
+        assert_eq!(info.kind, CallKind::Read);
+        assert_eq!(info.scope.as_deref(), Some("scottidler/gx"));
+        assert_eq!(info.resource, "pulls");
+        assert!(info.conditional);
+    }
+
+    #[test]
+    fn test_classify_api_mutations() {
+        assert_eq!(info(&["api", "-X", "PATCH", "repos/o/r"]).kind, CallKind::Mutation);
+        assert_eq!(info(&["api", "-X", "POST", "repos/o/r/git/refs", "-f", "ref=x"]).kind, CallKind::Mutation);
+        // Fields without an explicit method default to POST
+        assert_eq!(info(&["api", "repos/o/r/labels", "-f", "name=bug"]).kind, CallKind::Mutation);
+        assert_eq!(info(&["api", "graphql", "--input", "-"]).kind, CallKind::Passthrough);
+    }
+
+    #[test]
+    fn test_classify_api_not_conditional_with_jq() {
+        let info = info(&["api", "user", "--jq", ".login"]);
+        assert_eq!(info.kind, CallKind::Read);
+        assert_eq!(info.resource, "user");
+        assert!(!info.conditional);
+    }
+
+    #[test]
+    fn test_classify_commands() {
+        let view = info(&["pr", "view", "1", "--repo", "o/r", "--json", "title"]);
+        assert_eq!(view.kind, CallKind::Read);
+        assert_eq!(view.scope.as_deref(), Some("o/r"));
+        assert_eq!(view.resource, "pulls");
+
+        let merge = info(&["pr", "merge", "1", "--repo", "o/r", "--squash"]);
+        assert_eq!(merge.kind, CallKind::Mutation);
+        assert_eq!(merge.scope.as_deref(), Some("o/r"));
+
+        assert_eq!(info(&["repo", "view", "O/R"]).scope.as_deref(), Some("o/r"));
+        assert_eq!(info(&["search", "prs", "is:open"]).kind, CallKind::Read);
+        assert_eq!(info(&["run", "download", "1", "--repo", "o/r"]).kind, CallKind::Passthrough);
+    }
+
+    #[test]
+    fn test_ttl_per_resource() {
+        let mut config = CacheConfig::default();
+        config.ttl_secs.insert("contents".to_string(), 300);
+        let cache = ResponseCache::new(config);
+        assert_eq!(cache.ttl("contents"), Duration::from_secs(300));
+        assert_eq!(cache.ttl("pulls"), Duration::from_secs(CacheConfig::default().default_ttl_secs));
+    }
+
+    #[test]
+    fn test_lookup_fresh_and_stale() {
+        let mut config = CacheConfig::default();
+        config.ttl_secs.insert("pulls".to_string(), 0);
+        let cache = ResponseCache::new(config);
+
+        let repo_args = ["api", "repos/o/r"];
+        let repo_key = ResponseCache::key("home", &repo_args);
+        cache.store(&repo_key, &info(&repo_args), "{}", Some("\"abc\"".to_string()));
+        assert!(matches!(cache.lookup(&repo_key, &info(&repo_args)), Lookup::Fresh(_)));
+
+        let pr_args = ["api", "repos/o/r/pulls/1"];
+        let pr_key = ResponseCache::key("home", &pr_args);
+        cache.store(&pr_key, &info(&pr_args), "{}", Some("\"def\"".to_string()));
+        match cache.lookup(&pr_key, &info(&pr_args)) {
+            Lookup::Stale(entry) => assert_eq!(entry.etag.as_deref(), Some("\"def\"")),
+            other => panic!("expected stale entry, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_invalidate_scope() {
+        let cache = ResponseCache::new(CacheConfig::default());
+        let calls: [&[&str]; 4] = [
+            &["api", "repos/o/r/pulls/1"],
+            &["api", "repos/o/r/branches"],
+            &["api", "repos/o/other"],
+            &["search", "prs", "is:open"],
+        ];
+        for args in calls {
+            cache.store(&ResponseCache::key("home", args), &info(args), "{}", None);
+        }
+
+        cache.invalidate(Some("o/r"));
+
+        let remaining: Vec<bool> = calls
+            .iter()
+            .map(|args| !matches!(cache.lookup(&ResponseCache::key("home", args), &info(args)), Lookup::Miss))
+            .collect();
+        assert_eq!(remaining, vec![false, false, true, false]);
+    }
+
+    #[test]
+    fn test_disk_persistence() {
+        let dir = TempDir::new().unwrap();
+        let config = CacheConfig {
+            dir: Some(dir.path().display().to_string()),
+            ..CacheConfig::default()
+        };
+        let args = ["api", "repos/o/r/contents/README.md"];
+        let key = ResponseCache::key("home", &args);
+
+        ResponseCache::new(config.clone()).store(&key, &info(&args), "{\"a\":1}", None);
+
+        let reloaded = ResponseCache::new(config.clone());
+        assert!(matches!(reloaded.lookup(&key, &info(&args)), Lookup::Fresh(body) if body == "{\"a\":1}"));
+
+        reloaded.invalidate(Some("o/r"));
+        assert!(matches!(ResponseCache::new(config).lookup(&key, &info(&args)), Lookup::Miss));
+    }
+}
@@ -1,6 +1,6 @@
 //! CLI argument parsing for multi-account-github-mcp
 
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
 use std::path::PathBuf;
 
 /// Check if required tools are available and format their versions
@@ -58,4 +58,29 @@ pub enum Commands {
         #[arg(help = "Account name to test")]
         account: Option<String>,
     },
+
+    /// Show how repositories differ from a policy spec
+    Plan(PolicyArgs),
+
+    /// Change repositories to match a policy spec
+    Apply(PolicyArgs),
+}
+
+#[derive(Args)]
+pub struct PolicyArgs {
+    /// Path to the policy spec (YAML)
+    #[arg(help = "Path to the policy spec (YAML)")]
+    pub spec: PathBuf,
+
+    /// Account for targets that don't name one
+    #[arg(short, long, help = "Account for targets (or --repo) that don't name one")]
+    pub account: Option<String>,
+
+    /// Repositories to use instead of the spec's targets
+    #[arg(short, long = "repo", help = "Repository (owner/name, globs allowed) to use instead of the spec's targets")]
+    pub repos: Vec<String>,
+
+    /// Print JSON instead of a summary
+    #[arg(long, help = "Print JSON instead of a summary")]
+    pub json: bool,
 }
@@ -0,0 +1,184 @@
+//! Per-account concurrency limiting and coalescing of identical in-flight reads
+
+use crate::{Error, Result};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::{OnceCell, OwnedSemaphorePermit, Semaphore};
+
+/// Semaphore and queue depth for a single account
+#[derive(Debug)]
+struct AccountSlots {
+    semaphore: Arc<Semaphore>,
+    waiting: AtomicUsize,
+}
+
+/// Limits the number of concurrent gh processes per account
+#[derive(Debug)]
+pub struct AccountLimiter {
+    max: usize,
+    accounts: Mutex<HashMap<String, Arc<AccountSlots>>>,
+}
+
+impl AccountLimiter {
+    /// Create a limiter allowing `max` concurrent requests per account (0 = unlimited)
+    pub fn new(max: usize) -> Self {
+        let max = if max == 0 { Semaphore::MAX_PERMITS } else { max };
+        Self {
+            max,
+            accounts: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Wait for a free slot for the account
+    pub async fn acquire(&self, account: &str) -> OwnedSemaphorePermit {
+        let slots = self
+            .accounts
+            .lock()
+            .unwrap()
+            .entry(account.to_string())
+            .or_insert_with(|| {
+                Arc::new(AccountSlots {
+                    semaphore: Arc::new(Semaphore::new(self.max)),
+                    waiting: AtomicUsize::new(0),
+                })
+            })
+            .clone();
+
+        let queued = slots.waiting.fetch_add(1, Ordering::SeqCst) + 1;
+        let in_flight = self.max - slots.semaphore.available_permits();
+        if in_flight >= self.max {
+            tracing::debug!("Account {:?}: {} in flight, {} queued", account, in_flight, queued);
+        }
+
+        // The semaphore is never closed, so acquiring cannot fail
+        let permit = slots
+            .semaphore
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("account semaphore closed");
+        let queued = slots.waiting.fetch_sub(1, Ordering::SeqCst) - 1;
+
+        tracing::debug!(
+            "Account {:?}: {} in flight, {} queued",
+            account,
+            self.max - slots.semaphore.available_permits(),
+            queued
+        );
+        permit
+    }
+}
+
+type Shared = Arc<OnceCell<std::result::Result<String, String>>>;
+
+/// Shares one execution between identical requests that are in flight at the same time
+#[derive(Debug, Default)]
+pub struct Coalescer {
+    in_flight: Mutex<HashMap<String, Shared>>,
+}
+
+impl Coalescer {
+    /// Create an empty coalescer
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run `f` for `key`, or wait for the identical request already running
+    pub async fn run<F, Fut>(&self, key: &str, f: F) -> Result<String>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<String>>,
+    {
+        let cell = {
+            let mut in_flight = self.in_flight.lock().unwrap();
+            match in_flight.get(key) {
+                Some(cell) => {
+                    tracing::debug!("Coalescing with in-flight request");
+                    cell.clone()
+                }
+                None => {
+                    let cell = Shared::default();
+                    in_flight.insert(key.to_string(), cell.clone());
+                    cell
+                }
+            }
+        };
+
+        let result = cell
+            .get_or_init(|| async {
+                f().await.map_err(|e| match e {
+                    Error::GhCli(msg) => msg,
+                    other => other.to_string(),
+                })
+            })
+            .await
+            .clone();
+
+        let mut in_flight = self.in_flight.lock().unwrap();
+        if in_flight.get(key).is_some_and(|c| Arc::ptr_eq(c, &cell)) {
+            in_flight.remove(key);
+        }
+
+        result.map_err(Error::GhCli)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_coalescer_shares_execution() {
+        let coalescer = Coalescer::new();
+        let calls = AtomicUsize::new(0);
+
+        let call = || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            Ok("body".to_string())
+        };
+
+        let (a, b) = tokio::join!(coalescer.run("home:repos/o/r", call), coalescer.run("home:repos/o/r", call));
+        assert_eq!(a.unwrap(), "body");
+        assert_eq!(b.unwrap(), "body");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // Completed requests are not reused
+        coalescer.run("home:repos/o/r", call).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_coalescer_shares_errors() {
+        let coalescer = Coalescer::new();
+        let call = || async {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            Err(Error::GhCli("Not Found".to_string()))
+        };
+
+        let (a, b) = tokio::join!(coalescer.run("k", call), coalescer.run("k", call));
+        assert!(matches!(a, Err(Error::GhCli(msg)) if msg == "Not Found"));
+        assert!(matches!(b, Err(Error::GhCli(msg)) if msg == "Not Found"));
+    }
+
+    #[tokio::test]
+    async fn test_limiter_per_account() {
+        let limiter = AccountLimiter::new(1);
+        let held = limiter.acquire("home").await;
+
+        // A different account is not blocked
+        let other = tokio::time::timeout(Duration::from_millis(50), limiter.acquire("work")).await;
+        assert!(other.is_ok());
+
+        // The same account waits until the permit is released
+        let blocked = tokio::time::timeout(Duration::from_millis(50), limiter.acquire("home")).await;
+        assert!(blocked.is_err());
+
+        drop(held);
+        let freed = tokio::time::timeout(Duration::from_millis(50), limiter.acquire("home")).await;
+        assert!(freed.is_ok());
+    }
+}
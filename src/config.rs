@@ -1,5 +1,6 @@
 //! Configuration handling for multi-account-github-mcp
 
+use crate::settings::{BranchProtectionSettings, RepoSettings};
 use crate::{Error, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -22,6 +23,139 @@ fn default_log_level() -> String {
     "info".to_string()
 }
 
+/// Response cache configuration
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CacheConfig {
+    /// Enable response caching (default: true)
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// TTL in seconds for resource types without an explicit entry (default: 30).
+    /// Stale entries with an ETag are revalidated with If-None-Match.
+    #[serde(default = "default_cache_ttl")]
+    pub default_ttl_secs: u64,
+
+    /// Per-resource-type TTLs in seconds (e.g. repo, contents, pulls, branches, search)
+    #[serde(default)]
+    pub ttl_secs: HashMap<String, u64>,
+
+    /// Optional directory to persist cache entries across restarts (supports ~ expansion)
+    #[serde(default)]
+    pub dir: Option<String>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_cache_ttl() -> u64 {
+    30
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            default_ttl_secs: default_cache_ttl(),
+            ttl_secs: HashMap::new(),
+            dir: None,
+        }
+    }
+}
+
+/// Request concurrency configuration
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ConcurrencyConfig {
+    /// Maximum concurrent gh requests per account; 0 disables the limit (default: 8)
+    #[serde(default = "default_max_per_account")]
+    pub max_per_account: usize,
+
+    /// Share one gh call between identical in-flight reads (default: true)
+    #[serde(default = "default_true")]
+    pub coalesce: bool,
+}
+
+fn default_max_per_account() -> usize {
+    8
+}
+
+impl Default for ConcurrencyConfig {
+    fn default() -> Self {
+        Self {
+            max_per_account: default_max_per_account(),
+            coalesce: true,
+        }
+    }
+}
+
+/// Response size configuration
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct OutputConfig {
+    /// Maximum size in bytes of a single tool response; larger results are truncated at
+    /// item or file boundaries with a footer describing what was omitted. 0 disables the limit
+    /// (default: 50000)
+    #[serde(default = "default_max_response_bytes")]
+    pub max_response_bytes: usize,
+}
+
+fn default_max_response_bytes() -> usize {
+    50_000
+}
+
+impl Default for OutputConfig {
+    fn default() -> Self {
+        Self {
+            max_response_bytes: default_max_response_bytes(),
+        }
+    }
+}
+
+/// Diff retrieval configuration
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DiffConfig {
+    /// Glob patterns for files left out of PR diffs unless requested explicitly
+    /// (default: lockfiles, vendored and build output directories, minified assets)
+    #[serde(default = "default_diff_ignore")]
+    pub ignore: Vec<String>,
+}
+
+fn default_diff_ignore() -> Vec<String> {
+    [
+        "**/*.lock",
+        "**/package-lock.json",
+        "**/pnpm-lock.yaml",
+        "**/go.sum",
+        "**/vendor/**",
+        "**/node_modules/**",
+        "**/dist/**",
+        "**/*.min.js",
+        "**/*.min.css",
+    ]
+    .iter()
+    .map(|p| p.to_string())
+    .collect()
+}
+
+impl Default for DiffConfig {
+    fn default() -> Self {
+        Self {
+            ignore: default_diff_ignore(),
+        }
+    }
+}
+
+/// Defaults applied to repositories created with an account
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct RepoProfile {
+    /// Repository settings (merge options, features, ...) applied after creation
+    #[serde(flatten)]
+    pub settings: RepoSettings,
+
+    /// Protection applied to the default branch after creation
+    #[serde(default)]
+    pub branch_protection: Option<BranchProtectionSettings>,
+}
+
 /// Main configuration
 /// Simple format: accounts map directly to token file paths
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -36,6 +170,26 @@ pub struct Config {
     /// Logging configuration
     #[serde(default)]
     pub logging: LogConfig,
+
+    /// Response cache configuration
+    #[serde(default)]
+    pub cache: CacheConfig,
+
+    /// Request concurrency configuration
+    #[serde(default)]
+    pub concurrency: ConcurrencyConfig,
+
+    /// Response size configuration
+    #[serde(default)]
+    pub output: OutputConfig,
+
+    /// Diff retrieval configuration
+    #[serde(default)]
+    pub diff: DiffConfig,
+
+    /// Per-account defaults for newly created repositories, keyed by account name
+    #[serde(default)]
+    pub repo_profiles: HashMap<String, RepoProfile>,
 }
 
 fn default_account() -> String {
@@ -48,6 +202,11 @@ impl Default for Config {
             default_account: "default".to_string(),
             accounts: HashMap::new(),
             logging: LogConfig::default(),
+            cache: CacheConfig::default(),
+            concurrency: ConcurrencyConfig::default(),
+            output: OutputConfig::default(),
+            diff: DiffConfig::default(),
+            repo_profiles: HashMap::new(),
         }
     }
 }
@@ -113,6 +272,11 @@ impl Config {
             .ok_or_else(|| Error::AccountNotFound(account_name.to_string()))
     }
 
+    /// Get the repo profile for an account by name, or the default if None
+    pub fn repo_profile(&self, account: Option<&str>) -> Option<&RepoProfile> {
+        self.repo_profiles.get(account.unwrap_or(&self.default_account))
+    }
+
     /// Get the token for an account by reading the token file
     pub fn get_token(&self, account: Option<&str>) -> Result<String> {
         let token_path = self.get_token_path(account)?;
@@ -171,6 +335,76 @@ accounts:
         assert_eq!(config.accounts.len(), 2);
         assert!(config.accounts.contains_key("home"));
         assert!(config.accounts.contains_key("work"));
+        assert!(config.cache.enabled);
+    }
+
+    #[test]
+    fn test_load_cache_config() {
+        let yaml = r#"
+accounts:
+  home: ~/.config/github/tokens/personal
+cache:
+  default_ttl_secs: 10
+  ttl_secs:
+    contents: 300
+"#;
+
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(yaml.as_bytes()).unwrap();
+
+        let config = Config::load_from_file(file.path()).unwrap();
+        assert!(config.cache.enabled);
+        assert_eq!(config.cache.default_ttl_secs, 10);
+        assert_eq!(config.cache.ttl_secs.get("contents"), Some(&300));
+        assert!(config.cache.dir.is_none());
+        assert_eq!(config.concurrency.max_per_account, 8);
+        assert!(config.concurrency.coalesce);
+        assert_eq!(config.output.max_response_bytes, 50_000);
+    }
+
+    #[test]
+    fn test_load_output_config() {
+        let yaml = r#"
+accounts:
+  home: ~/.config/github/tokens/personal
+output:
+  max_response_bytes: 0
+"#;
+
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(yaml.as_bytes()).unwrap();
+
+        let config = Config::load_from_file(file.path()).unwrap();
+        assert_eq!(config.output.max_response_bytes, 0);
+        assert!(config.diff.ignore.contains(&"**/*.lock".to_string()));
+    }
+
+    #[test]
+    fn test_load_repo_profiles() {
+        let yaml = r#"
+default_account: work
+accounts:
+  work: ~/.config/github/tokens/work
+repo_profiles:
+  work:
+    allow_merge_commit: false
+    delete_branch_on_merge: true
+    branch_protection:
+      required_linear_history: true
+      required_pull_request_reviews:
+        required_approving_review_count: 1
+"#;
+
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(yaml.as_bytes()).unwrap();
+
+        let config = Config::load_from_file(file.path()).unwrap();
+        let profile = config.repo_profile(None).unwrap();
+        assert_eq!(profile.settings.allow_merge_commit, Some(false));
+        assert_eq!(profile.settings.delete_branch_on_merge, Some(true));
+        let protection = profile.branch_protection.as_ref().unwrap();
+        assert_eq!(protection.required_linear_history, Some(true));
+        assert!(config.repo_profile(Some("home")).is_none());
     }
 
     #[test]
@@ -181,6 +415,7 @@ accounts:
             default_account: "home".to_string(),
             accounts,
             logging: LogConfig::default(),
+            ..Config::default()
         };
         let path = config.get_token_path(None).unwrap();
         assert_eq!(path, "/path/to/token");
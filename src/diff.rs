@@ -0,0 +1,293 @@
+//! Unified diff parsing, file filtering and hunk paging
+
+use crate::{Error, Result};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+
+/// The diff of a single file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileDiff<'a> {
+    /// Path of the file after the change (before the change for deletions)
+    pub path: &'a str,
+
+    /// Full diff text for the file, starting at its `diff --git` line
+    pub text: &'a str,
+}
+
+impl<'a> FileDiff<'a> {
+    /// File header lines (`diff --git`, `index`, `---`, `+++`) before the first hunk
+    pub fn header(&self) -> &'a str {
+        match self.text.find("\n@@") {
+            Some(pos) => &self.text[..pos + 1],
+            None => self.text,
+        }
+    }
+
+    /// Hunks of the file, each starting at its `@@` line
+    pub fn hunks(&self) -> Vec<&'a str> {
+        let body = &self.text[self.header().len()..];
+        let mut starts = Vec::new();
+        let mut offset = 0;
+        for line in body.split_inclusive('\n') {
+            if line.starts_with("@@") {
+                starts.push(offset);
+            }
+            offset += line.len();
+        }
+
+        starts
+            .iter()
+            .enumerate()
+            .map(|(i, &start)| &body[start..starts.get(i + 1).copied().unwrap_or(body.len())])
+            .collect()
+    }
+}
+
+/// Compile glob patterns (e.g. `src/**/*.rs`, `**/*.lock`) into a matcher
+pub fn glob_set(patterns: &[String]) -> Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = Glob::new(pattern).map_err(|e| Error::Tool(format!("invalid glob '{pattern}': {e}")))?;
+        builder.add(glob);
+    }
+    builder.build().map_err(|e| Error::Tool(e.to_string()))
+}
+
+/// Files selected from a diff, and the paths skipped by the ignore list
+#[derive(Debug, Default)]
+pub struct Selection<'a> {
+    pub files: Vec<FileDiff<'a>>,
+    pub ignored: Vec<&'a str>,
+}
+
+/// Select files matching `include` (all files if None), skipping those matching `ignore`.
+///
+/// Files matched explicitly by `include` are kept even if they are on the ignore list.
+pub fn select_files<'a>(files: Vec<FileDiff<'a>>, include: Option<&GlobSet>, ignore: &GlobSet) -> Selection<'a> {
+    let mut selection = Selection::default();
+    for file in files {
+        match include {
+            Some(include) if include.is_match(file.path) => selection.files.push(file),
+            Some(_) => {}
+            None if ignore.is_match(file.path) => selection.ignored.push(file.path),
+            None => selection.files.push(file),
+        }
+    }
+    selection
+}
+
+/// A page of hunks taken from a list of files
+#[derive(Debug, PartialEq, Eq)]
+pub struct HunkPage {
+    /// Diff text of the page; each file's header is repeated before its first hunk on the page
+    pub text: String,
+
+    /// Index of the first hunk on the page
+    pub start: usize,
+
+    /// Index one past the last hunk on the page
+    pub end: usize,
+
+    /// Total number of hunks across all files
+    pub total: usize,
+}
+
+/// Take hunks starting at index `offset` while they fit in `budget` bytes (0 = unlimited).
+///
+/// Files without hunks (binary changes, pure renames, mode changes) count as a single
+/// header-only entry so they still appear. At least one entry is returned when any remain,
+/// so paging always makes progress.
+pub fn page_hunks(files: &[FileDiff<'_>], offset: usize, budget: usize) -> HunkPage {
+    let hunks: Vec<(&FileDiff<'_>, &str)> = files
+        .iter()
+        .flat_map(|file| {
+            let hunks = file.hunks();
+            let hunks = if hunks.is_empty() { vec![""] } else { hunks };
+            hunks.into_iter().map(move |hunk| (file, hunk))
+        })
+        .collect();
+
+    let mut text = String::new();
+    let mut end = offset.min(hunks.len());
+    let mut current: Option<&str> = None;
+    for (file, hunk) in hunks.iter().skip(offset) {
+        let header = if current == Some(file.path) { "" } else { file.header() };
+        let size = header.len() + hunk.len();
+        if budget > 0 && end > offset && text.len() + size > budget {
+            break;
+        }
+        text.push_str(header);
+        text.push_str(hunk);
+        current = Some(file.path);
+        end += 1;
+    }
+
+    HunkPage {
+        text,
+        start: offset.min(hunks.len()),
+        end,
+        total: hunks.len(),
+    }
+}
+
+/// Split a unified diff into per-file sections
+pub fn split_files(diff: &str) -> Vec<FileDiff<'_>> {
+    let mut starts: Vec<usize> = Vec::new();
+    let mut offset = 0;
+    for line in diff.split_inclusive('\n') {
+        if line.starts_with("diff --git ") {
+            starts.push(offset);
+        }
+        offset += line.len();
+    }
+
+    starts
+        .iter()
+        .enumerate()
+        .map(|(i, &start)| {
+            let end = starts.get(i + 1).copied().unwrap_or(diff.len());
+            let text = &diff[start..end];
+            FileDiff {
+                path: file_path(text),
+                text,
+            }
+        })
+        .collect()
+}
+
+/// Extract the file path from a `diff --git a/<old> b/<new>` section
+fn file_path(text: &str) -> &str {
+    // Prefer the +++/--- headers, which are unambiguous for paths containing spaces
+    for line in text.lines().take_while(|l| !l.starts_with("@@")) {
+        if let Some(path) = line.strip_prefix("+++ b/") {
+            return path;
+        }
+    }
+    for line in text.lines().take_while(|l| !l.starts_with("@@")) {
+        if let Some(path) = line.strip_prefix("--- a/") {
+            return path;
+        }
+    }
+
+    let header = text.lines().next().unwrap_or_default();
+    header
+        .rsplit_once(" b/")
+        .map(|(_, path)| path)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIFF: &str = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,3 +1,4 @@
+ pub mod a;
++pub mod b;
+ pub mod c;
+diff --git a/old name.txt b/old name.txt
+deleted file mode 100644
+index 3333333..0000000
+--- a/old name.txt
++++ /dev/null
+@@ -1 +0,0 @@
+-gone
+";
+
+    #[test]
+    fn test_split_files() {
+        let files = split_files(DIFF);
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].path, "src/lib.rs");
+        assert!(files[0].text.starts_with("diff --git a/src/lib.rs"));
+        assert!(files[0].text.ends_with(" pub mod c;\n"));
+        assert_eq!(files[1].path, "old name.txt");
+        assert_eq!(files.iter().map(|f| f.text.len()).sum::<usize>(), DIFF.len());
+    }
+
+    #[test]
+    fn test_hunks() {
+        let files = split_files(DIFF);
+        assert_eq!(files[0].header(), &DIFF[..files[0].text.find("@@").unwrap()]);
+        assert_eq!(files[0].hunks(), vec!["@@ -1,3 +1,4 @@\n pub mod a;\n+pub mod b;\n pub mod c;\n"]);
+        assert_eq!(files[1].hunks().len(), 1);
+    }
+
+    #[test]
+    fn test_select_files() {
+        let lockfile = "\
+diff --git a/Cargo.lock b/Cargo.lock
+--- a/Cargo.lock
++++ b/Cargo.lock
+@@ -1 +1 @@
+-a
++b
+";
+        let diff = format!("{DIFF}{lockfile}");
+        let ignore = glob_set(&["**/*.lock".to_string()]).unwrap();
+
+        let selection = select_files(split_files(&diff), None, &ignore);
+        assert_eq!(selection.files.len(), 2);
+        assert_eq!(selection.ignored, vec!["Cargo.lock"]);
+
+        // Explicit patterns override the ignore list
+        let include = glob_set(&["*.lock".to_string(), "src/*.rs".to_string()]).unwrap();
+        let selection = select_files(split_files(&diff), Some(&include), &ignore);
+        let paths: Vec<&str> = selection.files.iter().map(|f| f.path).collect();
+        assert_eq!(paths, vec!["src/lib.rs", "Cargo.lock"]);
+        assert!(selection.ignored.is_empty());
+
+        assert!(glob_set(&["src/[".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_page_hunks() {
+        let files = split_files(DIFF);
+
+        let page = page_hunks(&files, 0, 0);
+        assert_eq!((page.start, page.end, page.total), (0, 2, 2));
+        assert_eq!(page.text, DIFF);
+
+        // A tiny budget still returns one hunk, with its file header
+        let page = page_hunks(&files, 1, 10);
+        assert_eq!((page.start, page.end), (1, 2));
+        assert!(page.text.starts_with("diff --git a/old name.txt"));
+
+        let page = page_hunks(&files, 5, 10);
+        assert_eq!((page.start, page.end, page.total), (2, 2, 2));
+        assert!(page.text.is_empty());
+    }
+
+    #[test]
+    fn test_page_hunks_header_only_files() {
+        let diff = format!(
+            "{DIFF}\
+diff --git a/logo.png b/logo.png
+index 4444444..5555555 100644
+Binary files a/logo.png and b/logo.png differ
+diff --git a/old.rs b/new.rs
+similarity index 100%
+rename from old.rs
+rename to new.rs
+"
+        );
+        let files = split_files(&diff);
+        assert_eq!(files.len(), 4);
+
+        let page = page_hunks(&files, 0, 0);
+        assert_eq!(page.total, 4);
+        assert_eq!(page.text, diff);
+
+        let page = page_hunks(&files, 3, 10);
+        assert_eq!((page.start, page.end), (3, 4));
+        assert!(page.text.contains("rename to new.rs"));
+    }
+
+    #[test]
+    fn test_split_files_empty() {
+        assert!(split_files("").is_empty());
+    }
+}
@@ -22,6 +22,9 @@ pub enum Error {
     #[error("gh CLI not found. Install from https://cli.github.com")]
     GhNotFound,
 
+    #[error("GraphQL error: {0}")]
+    GraphQl(String),
+
     #[error("JSON parse error: {0}")]
     JsonParse(#[from] serde_json::Error),
 
@@ -37,3 +40,10 @@ pub enum Error {
     #[error("Tool error: {0}")]
     Tool(String),
 }
+
+impl Error {
+    /// True if gh reported an HTTP 404
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, Error::GhCli(msg) if msg.contains("Not Found") || msg.contains("HTTP 404"))
+    }
+}
@@ -0,0 +1,147 @@
+//! Running a request under every configured account and merging the results
+
+use crate::{Error, GhClient, Result};
+use globset::Glob;
+use serde_json::{Map, Value, json};
+use std::future::Future;
+use tokio::task::JoinSet;
+
+impl GhClient {
+    /// Run `f` concurrently for every configured account.
+    ///
+    /// `f` receives a handle to this client and the account name. Results are returned
+    /// sorted by account name; each account's gh calls still go through its concurrency limit.
+    pub async fn for_each_account<T, F, Fut>(&self, f: F) -> Vec<(String, Result<T>)>
+    where
+        T: Send + 'static,
+        F: Fn(GhClient, String) -> Fut,
+        Fut: Future<Output = Result<T>> + Send + 'static,
+    {
+        let mut tasks = JoinSet::new();
+        for account in self.config().accounts.keys() {
+            let account = account.clone();
+            let call = f(self.clone(), account.clone());
+            tasks.spawn(async move { (account, call.await) });
+        }
+
+        let mut results = Vec::new();
+        while let Some(joined) = tasks.join_next().await {
+            match joined {
+                Ok(result) => results.push(result),
+                Err(e) => tracing::warn!("Account task failed: {}", e),
+            }
+        }
+        results.sort_by(|a, b| a.0.cmp(&b.0));
+        results
+    }
+
+    /// Non-archived repositories of `owner` (as owner/name) whose name matches `name_glob` and that have `topic`
+    pub async fn find_repos(
+        &self,
+        account: Option<&str>,
+        owner: &str,
+        name_glob: Option<&str>,
+        topic: Option<&str>,
+    ) -> Result<Vec<String>> {
+        let glob = name_glob
+            .map(|pattern| {
+                Glob::new(pattern)
+                    .map(|glob| glob.compile_matcher())
+                    .map_err(|e| Error::Tool(format!("invalid repository name glob '{pattern}': {e}")))
+            })
+            .transpose()?;
+
+        let mut args = vec!["repo", "list", owner, "--no-archived", "--limit", "1000"];
+        if let Some(topic) = topic {
+            args.extend(["--topic", topic]);
+        }
+        args.extend(["--json", "name,nameWithOwner"]);
+        let listed = self.run(account, &args).await?;
+
+        Ok(listed
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|repo| {
+                let name = repo["name"].as_str().unwrap_or_default();
+                glob.as_ref().is_none_or(|glob| glob.is_match(name))
+            })
+            .filter_map(|repo| repo["nameWithOwner"].as_str().map(String::from))
+            .collect())
+    }
+}
+
+/// Merge per-account search hits, de-duplicating by `url`.
+///
+/// Each hit gains an `accounts` array listing the accounts that can see it. Accounts whose
+/// request failed are reported under `errors` instead of failing the whole search.
+pub fn merge_by_url(per_account: Vec<(String, Result<Value>)>) -> (Vec<Value>, Map<String, Value>) {
+    let mut hits: Vec<Value> = Vec::new();
+    let mut errors = Map::new();
+
+    for (account, result) in per_account {
+        let items = match result {
+            Ok(Value::Array(items)) => items,
+            Ok(_) => Vec::new(),
+            Err(e) => {
+                errors.insert(account, json!(e.to_string()));
+                continue;
+            }
+        };
+
+        for mut item in items {
+            let existing = item["url"]
+                .as_str()
+                .and_then(|url| hits.iter_mut().find(|hit| hit["url"] == url));
+            match existing {
+                Some(hit) => {
+                    if let Some(accounts) = hit["accounts"].as_array_mut() {
+                        accounts.push(json!(account));
+                    }
+                }
+                None => {
+                    if let Some(object) = item.as_object_mut() {
+                        object.insert("accounts".to_string(), json!([account]));
+                    }
+                    hits.push(item);
+                }
+            }
+        }
+    }
+
+    (hits, errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+
+    #[test]
+    fn test_merge_by_url() {
+        let per_account = vec![
+            (
+                "home".to_string(),
+                Ok(json!([
+                    {"url": "https://github.com/scottidler/gx/pull/1", "title": "a"},
+                    {"url": "https://github.com/tatari-tv/svc/pull/2", "title": "b"}
+                ])),
+            ),
+            ("oss".to_string(), Err(Error::GhCli("HTTP 401: Bad credentials".to_string()))),
+            (
+                "work".to_string(),
+                Ok(json!([
+                    {"url": "https://github.com/tatari-tv/svc/pull/2", "title": "b"},
+                    {"url": "https://github.com/tatari-tv/api/pull/3", "title": "c"}
+                ])),
+            ),
+        ];
+
+        let (hits, errors) = merge_by_url(per_account);
+        assert_eq!(hits.len(), 3);
+        assert_eq!(hits[0]["accounts"], json!(["home"]));
+        assert_eq!(hits[1]["accounts"], json!(["home", "work"]));
+        assert_eq!(hits[2]["accounts"], json!(["work"]));
+        assert_eq!(errors["oss"], "gh CLI error: HTTP 401: Bad credentials");
+    }
+}
@@ -1,15 +1,22 @@
 //! gh CLI wrapper for multi-account-github-mcp
 
+use crate::cache::{CallInfo, CallKind, Lookup, ResponseCache};
+use crate::concurrency::{AccountLimiter, Coalescer};
+use crate::secret::redact_args;
 use crate::{Config, Error, Result};
 use serde_json::Value;
-use std::process::Stdio;
+use std::process::{Output, Stdio};
 use std::sync::Arc;
+use tokio::io::AsyncWriteExt;
 use tokio::process::Command;
 
 /// Client for executing gh CLI commands with account-specific tokens
 #[derive(Debug, Clone)]
 pub struct GhClient {
     config: Arc<Config>,
+    cache: Arc<ResponseCache>,
+    limiter: Arc<AccountLimiter>,
+    in_flight: Arc<Coalescer>,
 }
 
 impl GhClient {
@@ -20,8 +27,13 @@ impl GhClient {
             return Err(Error::GhNotFound);
         }
 
+        let cache = ResponseCache::new(config.cache.clone());
+        let limiter = AccountLimiter::new(config.concurrency.max_per_account);
         Ok(Self {
             config: Arc::new(config),
+            cache: Arc::new(cache),
+            limiter: Arc::new(limiter),
+            in_flight: Arc::new(Coalescer::new()),
         })
     }
 
@@ -30,6 +42,12 @@ impl GhClient {
         &self.config
     }
 
+    /// Resolve an account's name and token
+    pub(crate) fn credentials<'a>(&'a self, account: Option<&'a str>) -> Result<(&'a str, String)> {
+        let token = self.config.get_token(account)?;
+        Ok((account.unwrap_or(&self.config.default_account), token))
+    }
+
     /// Run a gh command with the specified account's token
     ///
     /// # Arguments
@@ -39,42 +57,19 @@ impl GhClient {
     /// # Returns
     /// Parsed JSON output from gh command
     pub async fn run(&self, account: Option<&str>, args: &[&str]) -> Result<Value> {
-        let token = self.config.get_token(account)?;
-
-        tracing::debug!(
-            "Running gh command with account {:?}: gh {}",
-            account.unwrap_or("default"),
-            args.join(" ")
-        );
-
-        let output = Command::new("gh")
-            .args(args)
-            .env("GH_TOKEN", &token)
-            .env("NO_COLOR", "1")
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()
-            .map_err(|e| Error::GhCli(format!("Failed to spawn gh: {e}")))?
-            .wait_with_output()
-            .await
-            .map_err(|e| Error::GhCli(format!("Failed to wait for gh: {e}")))?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            let error_msg = if stderr.is_empty() { stdout.to_string() } else { stderr.to_string() };
-            return Err(Error::GhCli(error_msg.trim().to_string()));
-        }
-
-        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stdout = self.exec(account, args, None).await?;
+        Self::parse_json(&stdout)
+    }
 
+    /// Parse gh output as JSON, treating empty output (e.g. 204 No Content) as null
+    fn parse_json(stdout: &str) -> Result<Value> {
         // Handle empty output
         if stdout.trim().is_empty() {
             return Ok(Value::Null);
         }
 
         // Parse JSON output
-        let json: Value = serde_json::from_str(&stdout)
+        let json: Value = serde_json::from_str(stdout)
             .map_err(|e| Error::GhCli(format!("Failed to parse gh output as JSON: {e}\nOutput: {stdout}")))?;
 
         Ok(json)
@@ -82,36 +77,132 @@ impl GhClient {
 
     /// Run a gh command and return raw string output (for non-JSON commands like diff)
     pub async fn run_raw(&self, account: Option<&str>, args: &[&str]) -> Result<String> {
-        let token = self.config.get_token(account)?;
+        self.exec(account, args, None).await
+    }
+
+    /// Execute a gh command through the response cache.
+    ///
+    /// Reads are served from the cache while fresh; stale `gh api` GETs are revalidated
+    /// with If-None-Match so unchanged resources come back as 304s. Identical reads that
+    /// are in flight at the same time share one gh process. Mutations invalidate cached
+    /// entries for the repository they target. `input` is written to stdin (for `--input -`).
+    async fn exec(&self, account: Option<&str>, args: &[&str], input: Option<&str>) -> Result<String> {
+        let (account_name, token) = self.credentials(account)?;
+
+        tracing::debug!("Running gh command with account {:?}: gh {}", account_name, redact_args(args));
+
+        let info = CallInfo::classify(args);
+        match info.kind {
+            CallKind::Passthrough => Self::stdout(self.spawn(account_name, &token, args, input).await?),
+            CallKind::Mutation => {
+                let stdout = Self::stdout(self.spawn(account_name, &token, args, input).await?)?;
+                if self.cache.enabled() {
+                    self.cache.invalidate(info.scope.as_deref());
+                }
+                Ok(stdout)
+            }
+            CallKind::Read => {
+                let key = ResponseCache::key(account_name, args);
+                let read = || self.read(account_name, &token, &key, &info, args);
+                if self.config.concurrency.coalesce {
+                    self.in_flight.run(&key, read).await
+                } else {
+                    read().await
+                }
+            }
+        }
+    }
 
-        tracing::debug!(
-            "Running gh command (raw) with account {:?}: gh {}",
-            account.unwrap_or("default"),
-            args.join(" ")
-        );
+    /// Execute a read-only gh command, consulting the cache if enabled
+    async fn read(&self, account: &str, token: &str, key: &str, info: &CallInfo, args: &[&str]) -> Result<String> {
+        if !self.cache.enabled() {
+            return Self::stdout(self.spawn(account, token, args, None).await?);
+        }
 
-        let output = Command::new("gh")
+        let cached = match self.cache.lookup(key, info) {
+            Lookup::Fresh(body) => {
+                tracing::debug!("Cache hit: gh {}", redact_args(args));
+                return Ok(body);
+            }
+            Lookup::Stale(entry) => Some(entry),
+            Lookup::Miss => None,
+        };
+
+        if !info.conditional {
+            let stdout = Self::stdout(self.spawn(account, token, args, None).await?)?;
+            self.cache.store(key, info, &stdout, None);
+            return Ok(stdout);
+        }
+
+        // Conditional request: include response headers and send the cached ETag
+        let etag_header = cached
+            .as_ref()
+            .and_then(|e| e.etag.as_ref())
+            .map(|etag| format!("If-None-Match: {etag}"));
+        let mut conditional_args = vec!["api", "--include"];
+        if let Some(ref header) = etag_header {
+            conditional_args.push("-H");
+            conditional_args.push(header);
+        }
+        conditional_args.extend(&args[1..]);
+
+        let output = self.spawn(account, token, &conditional_args, None).await?;
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        match (parse_included(&stdout), cached) {
+            (Some(response), Some(entry)) if response.status == 304 => {
+                tracing::debug!("Cache revalidated (304): gh {}", redact_args(args));
+                Ok(self.cache.touch(key, entry))
+            }
+            (Some(response), _) if output.status.success() => {
+                self.cache.store(key, info, response.body, response.etag);
+                Ok(response.body.to_string())
+            }
+            _ => Err(Self::failure(&output)),
+        }
+    }
+
+    /// Spawn gh with the given token and wait for it to finish, within the account's concurrency limit.
+    /// `input` is written to stdin (for `--input -`).
+    pub(crate) async fn spawn(&self, account: &str, token: &str, args: &[&str], input: Option<&str>) -> Result<Output> {
+        let _permit = self.limiter.acquire(account).await;
+        let mut child = Command::new("gh")
             .args(args)
-            .env("GH_TOKEN", &token)
+            .env("GH_TOKEN", token)
             .env("NO_COLOR", "1")
+            .stdin(if input.is_some() { Stdio::piped() } else { Stdio::null() })
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()
-            .map_err(|e| Error::GhCli(format!("Failed to spawn gh: {e}")))?
+            .map_err(|e| Error::GhCli(format!("Failed to spawn gh: {e}")))?;
+
+        if let (Some(input), Some(mut stdin)) = (input, child.stdin.take()) {
+            stdin
+                .write_all(input.as_bytes())
+                .await
+                .map_err(|e| Error::GhCli(format!("Failed to write gh stdin: {e}")))?;
+        }
+
+        child
             .wait_with_output()
             .await
-            .map_err(|e| Error::GhCli(format!("Failed to wait for gh: {e}")))?;
+            .map_err(|e| Error::GhCli(format!("Failed to wait for gh: {e}")))
+    }
 
+    /// Return stdout of a successful gh invocation, or its error message
+    fn stdout(output: Output) -> Result<String> {
         if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            let error_msg = if stderr.is_empty() { stdout.to_string() } else { stderr.to_string() };
-            return Err(Error::GhCli(error_msg.trim().to_string()));
+            return Err(Self::failure(&output));
         }
-
         Ok(String::from_utf8_lossy(&output.stdout).to_string())
     }
 
+    pub(crate) fn failure(output: &Output) -> Error {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let error_msg = if stderr.is_empty() { stdout.to_string() } else { stderr.to_string() };
+        Error::GhCli(error_msg.trim().to_string())
+    }
+
     /// Run a gh api command
     ///
     /// # Arguments
@@ -145,6 +236,70 @@ impl GhClient {
         self.run(account, &args).await
     }
 
+    /// Run a gh api command with a JSON request body
+    ///
+    /// Unlike `api`, which sends string fields, the body is passed through `--input` so
+    /// nested objects, arrays, booleans and numbers keep their JSON types.
+    ///
+    /// # Arguments
+    /// * `account` - Optional account name
+    /// * `endpoint` - API endpoint (e.g., "repos/{owner}/{repo}/labels")
+    /// * `method` - HTTP method (POST, PUT, PATCH, DELETE)
+    /// * `body` - JSON request body
+    pub async fn api_json(&self, account: Option<&str>, endpoint: &str, method: &str, body: &Value) -> Result<Value> {
+        let body = serde_json::to_string(body)?;
+        let args = ["api", "-X", method, endpoint, "--input", "-"];
+        let stdout = self.exec(account, &args, Some(&body)).await?;
+        Self::parse_json(&stdout)
+    }
+
+    /// Fetch every item of a list endpoint, following pages of 100 until a short page
+    pub async fn api_list(&self, account: Option<&str>, endpoint: &str) -> Result<Vec<Value>> {
+        let separator = if endpoint.contains('?') { '&' } else { '?' };
+        let mut items = Vec::new();
+        for page in 1.. {
+            let endpoint = format!("{endpoint}{separator}per_page=100&page={page}");
+            let Value::Array(batch) = self.api(account, &endpoint, None, None).await? else {
+                break;
+            };
+            let last = batch.len() < 100;
+            items.extend(batch);
+            if last {
+                break;
+            }
+        }
+        Ok(items)
+    }
+
+    /// Fetch every item of a list endpoint that wraps its items as `{total_count, <field>: [...]}`.
+    ///
+    /// Pages hold `per_page` items, since some endpoints allow fewer than 100. Returns the same shape.
+    pub async fn api_list_wrapped(
+        &self,
+        account: Option<&str>,
+        endpoint: &str,
+        field: &str,
+        per_page: usize,
+    ) -> Result<Value> {
+        let separator = if endpoint.contains('?') { '&' } else { '?' };
+        let mut items = Vec::new();
+        let mut total = Value::Null;
+        for page in 1.. {
+            let endpoint = format!("{endpoint}{separator}per_page={per_page}&page={page}");
+            let mut response = self.api(account, &endpoint, None, None).await?;
+            total = response["total_count"].take();
+            let Value::Array(batch) = response[field].take() else {
+                break;
+            };
+            let last = batch.len() < per_page;
+            items.extend(batch);
+            if last || total.as_u64().is_some_and(|total| items.len() as u64 >= total) {
+                break;
+            }
+        }
+        Ok(serde_json::json!({ "total_count": total, field: items }))
+    }
+
     /// Check gh CLI version
     pub async fn version(&self) -> Result<String> {
         let output = Command::new("gh")
@@ -171,6 +326,42 @@ impl GhClient {
     }
 }
 
+/// A `gh api --include` response split into status, ETag and body
+#[derive(Debug, PartialEq, Eq)]
+struct IncludedResponse<'a> {
+    status: u16,
+    etag: Option<String>,
+    body: &'a str,
+}
+
+/// Parse the output of `gh api --include` (status line, headers, blank line, body)
+fn parse_included(stdout: &str) -> Option<IncludedResponse<'_>> {
+    let (head, body) = stdout
+        .split_once("\r\n\r\n")
+        .or_else(|| stdout.split_once("\n\n"))
+        .unwrap_or((stdout, ""));
+
+    let mut lines = head.lines();
+    let status = lines.next()?.split_whitespace().nth(1)?.parse().ok()?;
+    let etag = lines
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("etag"))
+        .map(|(_, value)| value.trim().to_string());
+
+    Some(IncludedResponse { status, etag, body })
+}
+
+/// Percent-encode a value for use as a URL path segment or query parameter
+pub fn encode_segment(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => (b as char).to_string(),
+            _ => format!("%{b:02X}"),
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -186,6 +377,7 @@ mod tests {
             default_account: "test".to_string(),
             accounts,
             logging: LogConfig::default(),
+            ..Config::default()
         }
     }
 
@@ -212,4 +404,27 @@ mod tests {
             assert!(version.unwrap().contains("gh version"));
         }
     }
+
+    #[test]
+    fn test_parse_included() {
+        let stdout = "HTTP/2.0 200 OK\r\nContent-Type: application/json\r\nEtag: W/\"abc123\"\r\n\r\n{\"name\":\"gx\"}";
+        let response = parse_included(stdout).unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(response.etag.as_deref(), Some("W/\"abc123\""));
+        assert_eq!(response.body, "{\"name\":\"gx\"}");
+    }
+
+    #[test]
+    fn test_parse_included_not_modified() {
+        let response = parse_included("HTTP/2.0 304 Not Modified\r\nEtag: \"abc\"\r\n\r\n").unwrap();
+        assert_eq!(response.status, 304);
+        assert_eq!(response.body, "");
+    }
+
+    #[test]
+    fn test_encode_segment() {
+        assert_eq!(encode_segment("good first issue"), "good%20first%20issue");
+        assert_eq!(encode_segment("type: bug/ui"), "type%3A%20bug%2Fui");
+        assert_eq!(encode_segment("v1.0"), "v1.0");
+    }
 }
@@ -0,0 +1,187 @@
+//! GraphQL support for GhClient
+
+use crate::{Error, GhClient, Result};
+use serde::Serialize;
+use serde_json::{Value, json};
+
+impl GhClient {
+    /// Run a GraphQL query or mutation
+    ///
+    /// # Arguments
+    /// * `account` - Optional account name; uses default if None
+    /// * `query` - GraphQL document
+    /// * `variables` - Variables for the document (any serializable value, typically a struct or `json!` object)
+    ///
+    /// # Returns
+    /// The `data` object of the response; a non-empty `errors` array becomes `Error::GraphQl`
+    pub async fn graphql<V: Serialize>(&self, account: Option<&str>, query: &str, variables: &V) -> Result<Value> {
+        let (account_name, token) = self.credentials(account)?;
+        let body = serde_json::to_string(&json!({ "query": query, "variables": variables }))?;
+
+        tracing::debug!(
+            "Running GraphQL {} with account {:?}",
+            operation_name(query).unwrap_or("query"),
+            account_name
+        );
+
+        let output = self
+            .spawn(account_name, &token, &["api", "graphql", "--input", "-"], Some(&body))
+            .await?;
+
+        // gh exits non-zero when the response has errors but still prints the body
+        match serde_json::from_str::<Value>(&String::from_utf8_lossy(&output.stdout)) {
+            Ok(response) if response.is_object() => response_data(response),
+            _ => Err(Self::failure(&output)),
+        }
+    }
+
+    /// Fetch every node of a paginated connection.
+    ///
+    /// The query must declare `$cursor: String`, pass `after: $cursor` to the connection at
+    /// `path` (e.g. `["repository", "pullRequests"]`), and select `nodes` and
+    /// `pageInfo { hasNextPage endCursor }`. Stops after `max_items` nodes if given.
+    pub async fn graphql_paginate<V: Serialize>(
+        &self,
+        account: Option<&str>,
+        query: &str,
+        variables: &V,
+        path: &[&str],
+        max_items: Option<usize>,
+    ) -> Result<Vec<Value>> {
+        let mut variables = serde_json::to_value(variables)?;
+        if !variables.is_object() {
+            return Err(Error::GraphQl("variables must be a JSON object".to_string()));
+        }
+
+        let mut nodes = Vec::new();
+        loop {
+            let data = self.graphql(account, query, &variables).await?;
+            let page = connection_page(&data, path)?;
+            nodes.extend(page.nodes);
+
+            if let Some(max) = max_items
+                && nodes.len() >= max
+            {
+                nodes.truncate(max);
+                break;
+            }
+
+            match page.end_cursor {
+                Some(cursor) if page.has_next_page => variables["cursor"] = json!(cursor),
+                _ => break,
+            }
+        }
+
+        Ok(nodes)
+    }
+}
+
+/// One page of a GraphQL connection
+#[derive(Debug, PartialEq)]
+pub struct ConnectionPage {
+    pub nodes: Vec<Value>,
+    pub has_next_page: bool,
+    pub end_cursor: Option<String>,
+}
+
+/// Extract the connection at `path` from a `data` object
+pub fn connection_page(data: &Value, path: &[&str]) -> Result<ConnectionPage> {
+    let connection = path
+        .iter()
+        .try_fold(data, |value, key| value.get(key).filter(|v| !v.is_null()))
+        .ok_or_else(|| Error::GraphQl(format!("connection not found at {}", path.join("."))))?;
+
+    Ok(ConnectionPage {
+        nodes: connection["nodes"].as_array().cloned().unwrap_or_default(),
+        has_next_page: connection["pageInfo"]["hasNextPage"].as_bool().unwrap_or(false),
+        end_cursor: connection["pageInfo"]["endCursor"].as_str().map(String::from),
+    })
+}
+
+/// Return the `data` of a GraphQL response, or its errors
+pub fn response_data(mut response: Value) -> Result<Value> {
+    if let Some(errors) = response["errors"].as_array().filter(|e| !e.is_empty()) {
+        let messages: Vec<String> = errors.iter().map(format_error).collect();
+        return Err(Error::GraphQl(messages.join("; ")));
+    }
+    Ok(response["data"].take())
+}
+
+fn format_error(error: &Value) -> String {
+    let message = error["message"].as_str().unwrap_or("unknown error");
+    let mut formatted = match error["type"].as_str() {
+        Some(kind) => format!("{kind}: {message}"),
+        None => message.to_string(),
+    };
+
+    if let Some(path) = error["path"].as_array() {
+        let path: Vec<String> = path
+            .iter()
+            .map(|p| p.as_str().map(String::from).unwrap_or_else(|| p.to_string()))
+            .collect();
+        formatted.push_str(&format!(" (at {})", path.join(".")));
+    }
+    formatted
+}
+
+/// Name of a GraphQL operation (`query Foo(...)` -> `Foo`), for logging
+fn operation_name(query: &str) -> Option<&str> {
+    let rest = query.trim_start();
+    let rest = rest.strip_prefix("query").or_else(|| rest.strip_prefix("mutation"))?;
+    let name = rest.trim_start().split(|c: char| !(c.is_alphanumeric() || c == '_')).next()?;
+    (!name.is_empty()).then_some(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_response_data() {
+        let response = json!({"data": {"viewer": {"login": "scottidler"}}});
+        let data = response_data(response).unwrap();
+        assert_eq!(data["viewer"]["login"], "scottidler");
+    }
+
+    #[test]
+    fn test_response_errors() {
+        let response = json!({
+            "data": {"repository": null},
+            "errors": [{
+                "type": "NOT_FOUND",
+                "path": ["repository"],
+                "message": "Could not resolve to a Repository with the name 'o/missing'."
+            }]
+        });
+        let err = response_data(response).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "GraphQL error: NOT_FOUND: Could not resolve to a Repository with the name 'o/missing'. (at repository)"
+        );
+    }
+
+    #[test]
+    fn test_connection_page() {
+        let data = json!({
+            "repository": {
+                "pullRequests": {
+                    "nodes": [{"number": 1}, {"number": 2}],
+                    "pageInfo": {"hasNextPage": true, "endCursor": "Y3Vyc29y"}
+                }
+            }
+        });
+        let page = connection_page(&data, &["repository", "pullRequests"]).unwrap();
+        assert_eq!(page.nodes.len(), 2);
+        assert!(page.has_next_page);
+        assert_eq!(page.end_cursor.as_deref(), Some("Y3Vyc29y"));
+
+        assert!(connection_page(&data, &["repository", "issues"]).is_err());
+    }
+
+    #[test]
+    fn test_operation_name() {
+        assert_eq!(operation_name("query PrOverview($owner: String!) { x }"), Some("PrOverview"));
+        assert_eq!(operation_name("mutation { resolveReviewThread }"), None);
+        assert_eq!(operation_name("{ viewer { login } }"), None);
+    }
+}
@@ -2,13 +2,23 @@
 //!
 //! A GitHub MCP server with multi-account support, wrapping the gh CLI.
 
+pub mod cache;
+pub mod concurrency;
 pub mod config;
+pub mod diff;
 pub mod error;
+pub mod fanout;
 pub mod gh;
+pub mod graphql;
 pub mod mcp;
+pub mod output;
+pub mod policy;
+pub mod protection;
+pub mod secret;
+pub mod settings;
 pub mod tools;
 
-pub use config::{Config, LogConfig};
+pub use config::{CacheConfig, ConcurrencyConfig, Config, DiffConfig, LogConfig, OutputConfig, RepoProfile};
 pub use error::Error;
 pub use gh::GhClient;
 
@@ -2,6 +2,7 @@
 
 use clap::Parser;
 use eyre::{Context, Result};
+use multi_account_github_mcp::policy::{self, PolicySpec};
 use multi_account_github_mcp::{Config, LogConfig, GhClient};
 use rmcp::ServiceExt;
 use std::io::{self, Write};
@@ -10,7 +11,7 @@ use tracing_subscriber::EnvFilter;
 
 mod cli;
 
-use cli::{Cli, Commands};
+use cli::{Cli, Commands, PolicyArgs};
 
 fn setup_logging(verbose: bool, log_config: &LogConfig) -> Result<()> {
     // Determine log level: CLI verbose flag overrides config
@@ -19,7 +20,8 @@ fn setup_logging(verbose: bool, log_config: &LogConfig) -> Result<()> {
     } else {
         &log_config.level
     };
-    let filter = EnvFilter::new(level);
+    // rmcp logs whole requests at debug level, which would include secret values
+    let filter = EnvFilter::new(multi_account_github_mcp::secret::log_filter(level));
 
     // If log file is configured, write to file; otherwise write to stderr
     if let Some(ref log_file) = log_config.file {
@@ -132,6 +134,30 @@ async fn run_test(config: Config, account: Option<String>) -> Result<()> {
     Ok(())
 }
 
+async fn run_policy(config: Config, args: PolicyArgs, apply: bool) -> Result<()> {
+    let spec = PolicySpec::load(&args.spec)
+        .and_then(|spec| spec.with_targets(args.account, args.repos))
+        .context("Failed to load policy")?;
+    let gh = GhClient::new(config).context("Failed to create GitHub client")?;
+
+    let mut plans = gh.plan_policy(spec).await.context("Failed to plan policy")?;
+    if apply {
+        plans = gh.apply_policy(plans).await;
+    }
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&plans)?);
+    } else {
+        print!("{}", policy::render_plan(&plans));
+    }
+
+    let failed = plans.iter().flat_map(|plan| &plan.changes).filter(|c| c.error.is_some()).count();
+    if failed > 0 {
+        eyre::bail!("{failed} change(s) failed to apply");
+    }
+    Ok(())
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
@@ -146,5 +172,7 @@ async fn main() -> Result<()> {
         Commands::Serve => run_serve(config).await,
         Commands::Accounts => run_accounts(&config),
         Commands::Test { account } => run_test(config, account).await,
+        Commands::Plan(args) => run_policy(config, args, false).await,
+        Commands::Apply(args) => run_policy(config, args, true).await,
     }
 }
@@ -2,35 +2,82 @@
 
 use tracing::{debug, info, warn};
 use crate::GhClient;
-use crate::tools::account::GetMeRequest;
+use crate::gh::encode_segment;
+use crate::{diff, fanout};
+use crate::output::{self, OutputOptions};
+use crate::protection::ProtectionChange;
+use crate::secret;
+use crate::settings::BranchProtectionSettings;
+use crate::tools::account::{DASHBOARD_SECTIONS, GetMeRequest, MyDashboardRequest, dashboard_item, shape_dashboard};
+use crate::tools::actions::{
+    ActionsScope, DeleteActionsItemRequest, ListActionsItemsRequest, SetSecretRequest, SetVariableRequest,
+};
+use crate::tools::batch::{self, BatchRequest, MAX_BATCH_SIZE};
 use crate::tools::branches::{CreateBranchRequest, DeleteBranchRequest, ListBranchesRequest};
-use crate::tools::code::{GetFileRequest, ListCommitsRequest, SearchCodeRequest};
-use crate::tools::protection::{DeleteBranchProtectionRequest, GetBranchProtectionRequest, SetBranchProtectionRequest};
+use crate::tools::code::{
+    BLAME_QUERY, BlameFileRequest, CompareRefsRequest, GetCommitRequest, GetFileHistoryRequest, GetFileRequest,
+    ListCommitsRequest, SearchCodeRequest, renamed_from, shape_blame, shape_commit, shape_comparison,
+};
+use crate::tools::environments::{
+    ApprovePendingDeploymentRequest, CreateDeploymentRequest, CreateDeploymentStatusRequest, DeleteEnvironmentRequest,
+    EnvironmentReviewer, ListDeploymentStatusesRequest, ListDeploymentsRequest, ListEnvironmentsRequest,
+    SetEnvironmentRequest, select_pending_environments,
+};
+use crate::tools::protection::{
+    DeleteBranchProtectionRequest, GetBranchProtectionRequest, SetBranchProtectionRequest,
+    ApplyBranchProtectionBulkRequest, UpdateBranchProtectionRequest,
+};
+use crate::tools::forks::{ForkRepoRequest, ListForksRequest, SyncForkRequest};
+use crate::tools::labels::{
+    AddLabelsRequest, CreateLabelRequest, CreateMilestoneRequest, DeleteLabelRequest, ListLabelsRequest,
+    ListMilestonesRequest, RemoveLabelRequest, SyncLabelsRequest, UpdateLabelRequest, UpdateMilestoneRequest,
+    label_body, plan_label_sync,
+};
+use crate::tools::notifications::{
+    ListNotificationsRequest, MarkAllReadRequest, MarkNotificationReadRequest, ThreadSubscriptionRequest,
+    merge_notifications, shape_notifications,
+};
+use crate::tools::policy::PolicyRequest;
 use crate::tools::prs::{
     ClosePrRequest, CommentPrRequest, CreatePrRequest, EditPrRequest, GetPrDiffRequest, GetPrFilesRequest,
-    GetPrRequest, ListPrsRequest, MergePrRequest, SearchPrsRequest,
+    GetPrOverviewRequest, GetPrRequest, ListPrsRequest, MergePrRequest, PrOverviewSection, SearchPrsRequest,
+    pr_overview_query, shape_pr_overview,
 };
 use crate::tools::releases::{
     CreateReleaseRequest, DeleteReleaseRequest, DownloadReleaseAssetRequest, GetReleaseRequest,
     ListReleaseAssetsRequest, ListReleasesRequest,
 };
-use crate::tools::repos::{ArchiveRepoRequest, CreateRepoRequest, GetRepoRequest, ListReposRequest};
+use crate::tools::repos::{
+    ArchiveRepoRequest, CreateRepoFromTemplateRequest, CreateRepoRequest, GetRepoRequest, ListReposRequest, RenameRepoRequest, TransferRepoRequest,
+    UnarchiveRepoRequest, UpdateRepoRequest,
+};
+use crate::tools::rulesets::{
+    CreateRulesetRequest, GetRulesForBranchRequest, ListRulesetsRequest, RulesetRequest, UpdateRulesetRequest,
+};
+use crate::tools::search::{SearchEverywhereRequest, SearchKind};
 use crate::tools::tags::{CreateTagRequest, DeleteTagRequest, ListTagsRequest};
 use crate::tools::teams::{
     AddCollaboratorRequest, GetTeamMembersRequest, ListCollaboratorsRequest, ListTeamsRequest,
     RemoveCollaboratorRequest,
 };
+use crate::tools::webhooks::{
+    CreateWebhookRequest, ListWebhookDeliveriesRequest, ListWebhooksRequest, UpdateWebhookRequest,
+    WebhookDeliveryRequest, WebhookRequest, delivery_failed,
+};
 use crate::tools::workflows::{DownloadRunArtifactRequest, ListRunArtifactsRequest, ListWorkflowRunsRequest};
-use rmcp::handler::server::tool::ToolRouter;
+use rmcp::handler::server::tool::{ToolCallContext, ToolRouter};
 use rmcp::handler::server::wrapper::Parameters;
-use rmcp::model::{CallToolResult, Content, ServerCapabilities, ServerInfo};
-use rmcp::{ErrorData as McpError, ServerHandler, tool, tool_handler, tool_router};
+use rmcp::model::{CallToolRequestParam, CallToolResult, Content, ServerCapabilities, ServerInfo};
+use rmcp::service::RequestContext;
+use rmcp::{ErrorData as McpError, RoleServer, ServerHandler, tool, tool_handler, tool_router};
+use serde_json::Value;
+use std::collections::HashMap;
+use tokio::task::JoinSet;
 
 /// GitHub MCP server with multi-account support
 #[derive(Clone)]
 pub struct GitHubMcpServer {
     gh: GhClient,
-    #[allow(dead_code)] // Used by rmcp tool_router macro
     tool_router: ToolRouter<Self>,
 }
 
@@ -50,8 +97,243 @@ impl GitHubMcpServer {
         warn!("Tool error: {}", e);
         McpError::internal_error(e.to_string(), None)
     }
+
+    /// Shape a JSON result with the caller's output options and wrap it as tool content
+    fn respond(&self, value: Value, output: &OutputOptions) -> Result<CallToolResult, McpError> {
+        self.respond_compact(value, output, &[])
+    }
+
+    /// Like `respond`, with a compact default projection used when the caller doesn't select fields
+    fn respond_compact(
+        &self,
+        value: Value,
+        output: &OutputOptions,
+        default_fields: &[&str],
+    ) -> Result<CallToolResult, McpError> {
+        self.respond_with(value, output, default_fields, DEFAULT_TRUNCATION_HINT)
+    }
+
+    /// Like `respond_compact`, with a tool-specific hint for fetching what the response budget cut off
+    fn respond_with(
+        &self,
+        value: Value,
+        output: &OutputOptions,
+        default_fields: &[&str],
+        hint: &str,
+    ) -> Result<CallToolResult, McpError> {
+        let value = output.apply(value, default_fields).map_err(Self::err)?;
+        let budget = self.gh.config().output.max_response_bytes;
+        let (value, omission) = output::fit_json(value, budget);
+
+        let mut content = vec![Content::json(&value)?];
+        if let Some(omission) = omission {
+            debug!("Truncated response: kept {} of {} {}", omission.kept, omission.total, omission.unit);
+            content.push(Content::text(omission.footer(budget, hint)));
+        }
+        Ok(CallToolResult::success(content))
+    }
+
+    /// Add the visibility fields org-level secrets and variables require
+    fn add_org_visibility(
+        body: &mut Value,
+        scope: &ActionsScope,
+        visibility: &Option<String>,
+        selected_repository_ids: &Option<Vec<u64>>,
+    ) {
+        if !scope.is_org() {
+            return;
+        }
+        body["visibility"] = serde_json::json!(visibility.as_deref().unwrap_or("private"));
+        if let Some(ids) = selected_repository_ids {
+            body["selected_repository_ids"] = serde_json::json!(ids);
+        }
+    }
+
+    /// Run a search under every configured account and merge the hits by URL.
+    ///
+    /// Field selection and jq apply to the merged hits; per-account failures go under `errors`.
+    async fn search_all_accounts(
+        &self,
+        kind: SearchKind,
+        query: &str,
+        limit: Option<u32>,
+        output: &OutputOptions,
+    ) -> Result<CallToolResult, McpError> {
+        let args = kind.args(query, limit);
+        let per_account = self
+            .gh
+            .for_each_account(move |gh, account| {
+                let args = args.clone();
+                async move {
+                    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+                    gh.run(Some(&account), &args).await
+                }
+            })
+            .await;
+
+        let (hits, errors) = fanout::merge_by_url(per_account);
+        let default_fields: Vec<&str> = match kind {
+            SearchKind::Code => SEARCH_CODE_FIELDS.iter().copied().chain(["accounts"]).collect(),
+            _ => Vec::new(),
+        };
+        let hits = output.apply(Value::Array(hits), &default_fields).map_err(Self::err)?;
+
+        let hint = match kind {
+            SearchKind::Code => SEARCH_CODE_HINT,
+            _ => DEFAULT_TRUNCATION_HINT,
+        };
+        let result = serde_json::json!({ "results": hits, "errors": errors });
+        self.respond_with(result, &OutputOptions::default(), &[], hint)
+    }
+
+    /// Apply the account's repo profile to a newly created repository.
+    ///
+    /// Failures don't fail the creation; they are reported under `profile.errors` in `repo`.
+    /// `generated` repositories are filled in asynchronously, so branch protection waits for the default branch.
+    async fn apply_repo_profile(&self, account: Option<&str>, repo: &mut Value, generated: bool) {
+        let Some(profile) = self.gh.config().repo_profile(account) else {
+            return;
+        };
+        let full_name = repo["full_name"].as_str().unwrap_or_default().to_string();
+        let Some((owner, name)) = full_name.split_once('/') else {
+            return;
+        };
+        debug!("Applying repo profile to {}", full_name);
+
+        let mut applied = Vec::new();
+        let mut errors = Vec::new();
+
+        let settings = serde_json::to_value(&profile.settings).unwrap_or_default();
+        if settings.as_object().is_some_and(|s| !s.is_empty()) {
+            match self
+                .gh
+                .api_json(account, &format!("repos/{full_name}"), "PATCH", &settings)
+                .await
+            {
+                Ok(updated) => {
+                    applied.push("settings");
+                    *repo = updated;
+                }
+                Err(e) => errors.push(format!("settings: {e}")),
+            }
+        }
+
+        if let Some(ref protection) = profile.branch_protection {
+            let branch = repo["default_branch"].as_str().unwrap_or("main").to_string();
+            let ready = if generated {
+                self.gh.wait_for_branch(account, owner, name, &branch, TEMPLATE_BRANCH_ATTEMPTS).await
+            } else {
+                Ok(true)
+            };
+            let protected = match ready {
+                Ok(true) => match self.gh.protect_branch(account, owner, name, &branch, protection).await {
+                    Ok(_) => Ok(()),
+                    Err(e) => Err(e.to_string()),
+                },
+                Ok(false) => Err(format!("branch did not appear within {TEMPLATE_BRANCH_ATTEMPTS} seconds")),
+                Err(e) => Err(e.to_string()),
+            };
+            match protected {
+                Ok(()) => applied.push("branch_protection"),
+                // An empty repository has no branch to protect yet
+                Err(e) => errors.push(format!("branch_protection on '{branch}': {e}")),
+            }
+        }
+
+        repo["profile"] = serde_json::json!({ "applied": applied, "errors": errors });
+    }
+}
+
+/// Fetch notifications page by page until `limit` have the given reason or there are no more.
+///
+/// GitHub can't filter by reason, so without one a single page of `limit` usually suffices.
+/// Each notification is tagged with `tag` as its account, if given.
+async fn collect_notifications(
+    gh: &GhClient,
+    account: Option<&str>,
+    endpoint: &str,
+    reason: Option<&str>,
+    limit: usize,
+    tag: Option<&str>,
+) -> crate::Result<Vec<Value>> {
+    // The notifications API returns at most 50 per page
+    let per_page = if reason.is_some() { 50 } else { limit.clamp(1, 50) };
+    let separator = if endpoint.contains('?') { '&' } else { '?' };
+    let mut matched = Vec::new();
+    for page in 1.. {
+        let endpoint = format!("{endpoint}{separator}per_page={per_page}&page={page}");
+        let notifications = gh.api(account, &endpoint, None, None).await?;
+        let fetched = notifications.as_array().map_or(0, Vec::len);
+        matched.extend(shape_notifications(&notifications, reason, tag));
+        if matched.len() >= limit || fetched < per_page {
+            break;
+        }
+    }
+    matched.truncate(limit);
+    Ok(matched)
+}
+
+/// Gather one account's dashboard: its login plus each section's search results.
+///
+/// A failed section is recorded under the dashboard's `errors` so the other sections still show.
+async fn account_dashboard(gh: GhClient, account: String, limit: u32) -> crate::Result<Value> {
+    let account = Some(account.as_str());
+    let user = gh.api(account, "user", None, None).await?;
+
+    let search = |section: usize| {
+        let (_, kind, query) = DASHBOARD_SECTIONS[section];
+        let args = kind.args(query, Some(limit));
+        let gh = &gh;
+        async move {
+            let args: Vec<&str> = args.iter().map(String::as_str).collect();
+            gh.run(account, &args).await
+        }
+    };
+    let (authored, review, assigned, failing) = tokio::join!(search(0), search(1), search(2), search(3));
+
+    let mut dashboard = serde_json::json!({ "login": user["login"] });
+    let mut errors = serde_json::Map::new();
+    for ((name, _, _), result) in DASHBOARD_SECTIONS.iter().zip([authored, review, assigned, failing]) {
+        let items = match result {
+            Ok(items) => items,
+            Err(e) => {
+                errors.insert(name.to_string(), Value::String(e.to_string()));
+                continue;
+            }
+        };
+        let items: Vec<Value> = items
+            .as_array()
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+            .iter()
+            .map(dashboard_item)
+            .collect();
+        dashboard[*name] = Value::Array(items);
+    }
+    if !errors.is_empty() {
+        dashboard["errors"] = Value::Object(errors);
+    }
+    Ok(dashboard)
 }
 
+/// Compact projection for code search hits
+const SEARCH_CODE_FIELDS: &[&str] = &["path", "repository.nameWithOwner", "url", "textMatches.fragment"];
+
+/// Footer hint for truncated code search results
+const SEARCH_CODE_HINT: &str =
+    "Lower 'limit', add qualifiers such as 'repo:' or 'path:' to the query, or drop textMatches from 'fields'.";
+
+/// Footer hint for file contents cut by the response budget
+const GET_FILE_HINT: &str =
+    "Use blame_file with start_line/end_line to read a line range, or pick a smaller file with 'path' or 'ref'.";
+
+/// Seconds to wait for a repository generated from a template to get its default branch
+const TEMPLATE_BRANCH_ATTEMPTS: u32 = 30;
+
+/// Footer hint for results cut by the response budget
+const DEFAULT_TRUNCATION_HINT: &str =
+    "Narrow the result with 'fields' or 'jq', or request fewer items with 'limit' or a more specific query.";
+
 #[tool_router]
 impl GitHubMcpServer {
     // ============================================
@@ -68,7 +350,20 @@ impl GitHubMcpServer {
             .run(params.0.account.as_deref(), &["api", "user"])
             .await
             .map_err(Self::err)?;
-        Ok(CallToolResult::success(vec![Content::json(&result)?]))
+        self.respond(result, &params.0.output)
+    }
+
+    /// Summarize open work across all accounts
+    #[tool(
+        description = "Morning dashboard across every configured account: open PRs I authored, PRs awaiting my review, issues assigned to me, and my open PRs with failing checks. Returns per-account counts under 'summary' and one 'items' list tagged with account and section; failed sections are listed under the account's 'errors' in the summary."
+    )]
+    async fn my_dashboard(&self, params: Parameters<MyDashboardRequest>) -> Result<CallToolResult, McpError> {
+        let limit = params.0.limit.unwrap_or(20);
+        let per_account = self
+            .gh
+            .for_each_account(move |gh, account| account_dashboard(gh, account, limit))
+            .await;
+        self.respond(shape_dashboard(per_account), &params.0.output)
     }
 
     // ============================================
@@ -76,30 +371,59 @@ impl GitHubMcpServer {
     // ============================================
 
     /// Create a new GitHub repository
-    #[tool(description = "Create a new GitHub repository. Can create personal or organization repos.")]
+    #[tool(
+        description = "Create a new GitHub repository, personal or in an organization. Can initialize it with a README, .gitignore and license, and grant a team access. The account's repo profile (merge options, default branch protection) is applied afterwards unless apply_profile is false."
+    )]
     async fn create_repo(&self, params: Parameters<CreateRepoRequest>) -> Result<CallToolResult, McpError> {
-        let mut args = vec!["repo", "create", &params.0.name];
+        let account = params.0.account.as_deref();
+        let endpoint = match params.0.org {
+            Some(ref org) => format!("orgs/{org}/repos"),
+            None => "user/repos".to_string(),
+        };
+        let mut result = self
+            .gh
+            .api_json(account, &endpoint, "POST", &params.0.to_body())
+            .await
+            .map_err(Self::err)?;
 
-        let description;
-        if let Some(ref desc) = params.0.description {
-            description = format!("--description={desc}");
-            args.push(&description);
+        if params.0.apply_profile.unwrap_or(true) {
+            self.apply_repo_profile(account, &mut result, false).await;
         }
+        self.respond(result, &params.0.output)
+    }
 
-        if params.0.private.unwrap_or(false) {
-            args.push("--private");
-        } else {
-            args.push("--public");
+    /// Create a repository from a template
+    #[tool(
+        description = "Create a new repository from a template repository. The account's repo profile (merge options, default branch protection) is applied afterwards unless apply_profile is false."
+    )]
+    async fn create_repo_from_template(
+        &self,
+        params: Parameters<CreateRepoFromTemplateRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let account = params.0.account.as_deref();
+        let endpoint = format!("repos/{}/{}/generate", params.0.template_owner, params.0.template_repo);
+        let mut body = serde_json::json!({
+            "name": params.0.name,
+            "private": params.0.private.unwrap_or(false),
+            "include_all_branches": params.0.include_all_branches.unwrap_or(false),
+        });
+        if let Some(ref owner) = params.0.owner {
+            body["owner"] = Value::String(owner.clone());
+        }
+        if let Some(ref description) = params.0.description {
+            body["description"] = Value::String(description.clone());
         }
 
-        args.push("--confirm");
-
-        let result = self
+        let mut result = self
             .gh
-            .run(params.0.account.as_deref(), &args)
+            .api_json(account, &endpoint, "POST", &body)
             .await
             .map_err(Self::err)?;
-        Ok(CallToolResult::success(vec![Content::json(&result)?]))
+
+        if params.0.apply_profile.unwrap_or(true) {
+            self.apply_repo_profile(account, &mut result, true).await;
+        }
+        self.respond(result, &params.0.output)
     }
 
     /// List repositories for a user or organization
@@ -126,7 +450,7 @@ impl GitHubMcpServer {
             .run(params.0.account.as_deref(), &args)
             .await
             .map_err(Self::err)?;
-        Ok(CallToolResult::success(vec![Content::json(&result)?]))
+        self.respond(result, &params.0.output)
     }
 
     /// Get details about a specific repository
@@ -145,7 +469,7 @@ impl GitHubMcpServer {
             .run(params.0.account.as_deref(), &args)
             .await
             .map_err(Self::err)?;
-        Ok(CallToolResult::success(vec![Content::json(&result)?]))
+        self.respond(result, &params.0.output)
     }
 
     /// Archive a repository (safer alternative to deletion)
@@ -154,17 +478,212 @@ impl GitHubMcpServer {
     )]
     async fn archive_repo(&self, params: Parameters<ArchiveRepoRequest>) -> Result<CallToolResult, McpError> {
         let endpoint = format!("repos/{}/{}", params.0.owner, params.0.repo);
+        let body = serde_json::json!({ "archived": true });
         let result = self
             .gh
-            .api(
-                params.0.account.as_deref(),
-                &endpoint,
-                Some("PATCH"),
-                Some(&[("archived", "true")]),
+            .api_json(params.0.account.as_deref(), &endpoint, "PATCH", &body)
+            .await
+            .map_err(Self::err)?;
+        self.respond(result, &params.0.output)
+    }
+
+    /// Unarchive a repository
+    #[tool(description = "Unarchive a repository, making it writable again.")]
+    async fn unarchive_repo(&self, params: Parameters<UnarchiveRepoRequest>) -> Result<CallToolResult, McpError> {
+        let endpoint = format!("repos/{}/{}", params.0.owner, params.0.repo);
+        let body = serde_json::json!({ "archived": false });
+        let result = self
+            .gh
+            .api_json(params.0.account.as_deref(), &endpoint, "PATCH", &body)
+            .await
+            .map_err(Self::err)?;
+        self.respond(result, &params.0.output)
+    }
+
+    /// Update repository settings
+    #[tool(
+        description = "Update repository settings: description, homepage, visibility, default branch, topics, features (issues/wiki/projects/discussions) and merge options (squash/merge commit/rebase, auto-merge, delete branch on merge). Only the given settings change."
+    )]
+    async fn update_repo(&self, params: Parameters<UpdateRepoRequest>) -> Result<CallToolResult, McpError> {
+        let account = params.0.account.as_deref();
+        let endpoint = format!("repos/{}/{}", params.0.owner, params.0.repo);
+        let body = serde_json::to_value(&params.0.settings).map_err(Self::err)?;
+        let has_settings = body.as_object().is_some_and(|b| !b.is_empty());
+        if !has_settings && params.0.topics.is_none() {
+            return Err(Self::err("no settings to update"));
+        }
+
+        // Topics have their own endpoint; set them first so the returned repo reflects them
+        if let Some(ref topics) = params.0.topics {
+            let body = serde_json::json!({ "names": topics });
+            self.gh
+                .api_json(account, &format!("{endpoint}/topics"), "PUT", &body)
+                .await
+                .map_err(Self::err)?;
+        }
+
+        let result = if has_settings {
+            self.gh.api_json(account, &endpoint, "PATCH", &body).await
+        } else {
+            self.gh.api(account, &endpoint, None, None).await
+        }
+        .map_err(Self::err)?;
+        self.respond(result, &params.0.output)
+    }
+
+    /// Rename a repository
+    #[tool(description = "Rename a repository. GitHub redirects the old name to the new one.")]
+    async fn rename_repo(&self, params: Parameters<RenameRepoRequest>) -> Result<CallToolResult, McpError> {
+        let endpoint = format!("repos/{}/{}", params.0.owner, params.0.repo);
+        let body = serde_json::json!({ "name": params.0.new_name });
+        let result = self
+            .gh
+            .api_json(params.0.account.as_deref(), &endpoint, "PATCH", &body)
+            .await
+            .map_err(Self::err)?;
+        self.respond(result, &params.0.output)
+    }
+
+    /// Transfer a repository to another user or organization
+    #[tool(
+        description = "Transfer a repository to another user or organization. Transfers to a user must be accepted by that user; transfers to an org require permission to create repos there."
+    )]
+    async fn transfer_repo(&self, params: Parameters<TransferRepoRequest>) -> Result<CallToolResult, McpError> {
+        let endpoint = format!("repos/{}/{}/transfer", params.0.owner, params.0.repo);
+        let mut body = serde_json::json!({ "new_owner": params.0.new_owner });
+        if let Some(ref new_name) = params.0.new_name {
+            body["new_name"] = Value::String(new_name.clone());
+        }
+        if let Some(ref team_ids) = params.0.team_ids {
+            body["team_ids"] = serde_json::json!(team_ids);
+        }
+
+        let result = self
+            .gh
+            .api_json(params.0.account.as_deref(), &endpoint, "POST", &body)
+            .await
+            .map_err(Self::err)?;
+        self.respond(result, &params.0.output)
+    }
+
+    // ============================================
+    // Fork Tools
+    // ============================================
+
+    /// Fork a repository, optionally into another account
+    #[tool(
+        description = "Fork a repository. Set destination_account to fork with a different account than the one that can read the source (e.g. fork a 'work' repo into 'home'). If the destination can't see a private source, grant_access invites it as a read collaborator using 'account' and accepts the invitation first."
+    )]
+    async fn fork_repo(&self, params: Parameters<ForkRepoRequest>) -> Result<CallToolResult, McpError> {
+        let source_account = params.0.account.as_deref();
+        let destination = params.0.destination_account.as_deref().or(source_account);
+        let source = format!("repos/{}/{}", params.0.owner, params.0.repo);
+
+        let visible = match self.gh.api(destination, &source, None, None).await {
+            Ok(_) => true,
+            Err(e) if e.is_not_found() => false,
+            Err(e) => return Err(Self::err(e)),
+        };
+
+        if !visible {
+            let destination_name = destination.unwrap_or(&self.gh.config().default_account);
+            if !params.0.grant_access.unwrap_or(false) || destination == source_account {
+                return Err(Self::err(format!(
+                    "account '{destination_name}' cannot access {}/{}; pass grant_access=true with an 'account' that can, to invite it as a read collaborator first",
+                    params.0.owner, params.0.repo
+                )));
+            }
+
+            let user = self.gh.api(destination, "user", None, None).await.map_err(Self::err)?;
+            let login = user["login"].as_str().unwrap_or_default();
+            let invitation = self
+                .gh
+                .api_json(
+                    source_account,
+                    &format!("{source}/collaborators/{login}"),
+                    "PUT",
+                    &serde_json::json!({ "permission": "pull" }),
+                )
+                .await
+                .map_err(Self::err)?;
+
+            // 204 (no invitation) means the user already has access
+            if let Some(id) = invitation["id"].as_u64() {
+                debug!("Accepting invitation {} to {} as {}", id, source, login);
+                self.gh
+                    .api(destination, &format!("user/repository_invitations/{id}"), Some("PATCH"), None)
+                    .await
+                    .map_err(Self::err)?;
+            }
+        }
+
+        let mut body = serde_json::json!({
+            "default_branch_only": params.0.default_branch_only.unwrap_or(false),
+        });
+        if let Some(ref org) = params.0.org {
+            body["organization"] = Value::String(org.clone());
+        }
+        if let Some(ref name) = params.0.name {
+            body["name"] = Value::String(name.clone());
+        }
+
+        let result = self
+            .gh
+            .api_json(destination, &format!("{source}/forks"), "POST", &body)
+            .await
+            .map_err(Self::err)?;
+        self.respond(result, &params.0.output)
+    }
+
+    /// List forks of a repository
+    #[tool(description = "List forks of a repository.")]
+    async fn list_forks(&self, params: Parameters<ListForksRequest>) -> Result<CallToolResult, McpError> {
+        let endpoint = format!(
+            "repos/{}/{}/forks?sort={}&per_page={}",
+            params.0.owner,
+            params.0.repo,
+            params.0.sort.as_deref().unwrap_or("newest"),
+            params.0.limit.unwrap_or(30)
+        );
+        let result = self
+            .gh
+            .api(params.0.account.as_deref(), &endpoint, None, None)
+            .await
+            .map_err(Self::err)?;
+        self.respond_compact(
+            result,
+            &params.0.output,
+            &["full_name", "owner.login", "private", "default_branch", "pushed_at", "html_url"],
+        )
+    }
+
+    /// Sync a fork branch with its upstream
+    #[tool(
+        description = "Update a branch of a fork from its upstream repository (merge-upstream). Fails if the branch has diverged in a way that can't be fast-forwarded or merged cleanly."
+    )]
+    async fn sync_fork(&self, params: Parameters<SyncForkRequest>) -> Result<CallToolResult, McpError> {
+        let account = params.0.account.as_deref();
+        let endpoint = format!("repos/{}/{}", params.0.owner, params.0.repo);
+
+        let branch = match params.0.branch {
+            Some(ref branch) => branch.clone(),
+            None => {
+                let fork = self.gh.api(account, &endpoint, None, None).await.map_err(Self::err)?;
+                fork["default_branch"].as_str().unwrap_or("main").to_string()
+            }
+        };
+
+        let result = self
+            .gh
+            .api_json(
+                account,
+                &format!("{endpoint}/merge-upstream"),
+                "POST",
+                &serde_json::json!({ "branch": branch }),
             )
             .await
             .map_err(Self::err)?;
-        Ok(CallToolResult::success(vec![Content::json(&result)?]))
+        self.respond(result, &params.0.output)
     }
 
     // ============================================
@@ -180,7 +699,7 @@ impl GitHubMcpServer {
             .api(params.0.account.as_deref(), &endpoint, None, None)
             .await
             .map_err(Self::err)?;
-        Ok(CallToolResult::success(vec![Content::json(&result)?]))
+        self.respond_compact(result, &params.0.output, &["name", "commit.sha", "protected"])
     }
 
     /// Create a new branch in a repository
@@ -215,7 +734,7 @@ impl GitHubMcpServer {
             .run(params.0.account.as_deref(), &args)
             .await
             .map_err(Self::err)?;
-        Ok(CallToolResult::success(vec![Content::json(&result)?]))
+        self.respond(result, &params.0.output)
     }
 
     /// Delete a branch from a repository
@@ -240,21 +759,26 @@ impl GitHubMcpServer {
     // ============================================
 
     /// Get branch protection rules for a branch
-    #[tool(description = "Get the branch protection rules for a specific branch.")]
+    #[tool(
+        description = "Get the branch protection rules for a specific branch, in the same shape set_branch_protection and update_branch_protection accept."
+    )]
     async fn get_branch_protection(
         &self,
         params: Parameters<GetBranchProtectionRequest>,
     ) -> Result<CallToolResult, McpError> {
         let endpoint = format!(
             "repos/{}/{}/branches/{}/protection",
-            params.0.owner, params.0.repo, params.0.branch
+            params.0.owner,
+            params.0.repo,
+            encode_segment(&params.0.branch)
         );
         let result = self
             .gh
             .api(params.0.account.as_deref(), &endpoint, None, None)
             .await
             .map_err(Self::err)?;
-        Ok(CallToolResult::success(vec![Content::json(&result)?]))
+        let settings = BranchProtectionSettings::from_api(&result);
+        self.respond(serde_json::json!(settings), &params.0.output)
     }
 
     /// Set branch protection rules for a branch
@@ -265,77 +789,99 @@ impl GitHubMcpServer {
         &self,
         params: Parameters<SetBranchProtectionRequest>,
     ) -> Result<CallToolResult, McpError> {
-        let endpoint = format!(
-            "repos/{}/{}/branches/{}/protection",
-            params.0.owner, params.0.repo, params.0.branch
-        );
+        let result = self
+            .gh
+            .protect_branch(
+                params.0.account.as_deref(),
+                &params.0.owner,
+                &params.0.repo,
+                &params.0.branch,
+                &params.0.settings,
+            )
+            .await
+            .map_err(Self::err)?;
+        let settings = BranchProtectionSettings::from_api(&result);
+        self.respond(serde_json::json!(settings), &params.0.output)
+    }
 
-        // Build the protection settings JSON
-        let mut body = serde_json::json!({});
+    /// Update individual branch protection settings
+    #[tool(
+        description = "Change individual branch protection settings, keeping every other setting as it is (set_branch_protection replaces all of them). Can add or remove required status check contexts."
+    )]
+    async fn update_branch_protection(
+        &self,
+        params: Parameters<UpdateBranchProtectionRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let change = ProtectionChange::Update {
+            settings: params.0.settings,
+            add_status_checks: params.0.add_status_checks.unwrap_or_default(),
+            remove_status_checks: params.0.remove_status_checks.unwrap_or_default(),
+        };
+        let result = self
+            .gh
+            .change_protection(
+                params.0.account.as_deref(),
+                &params.0.owner,
+                &params.0.repo,
+                &params.0.branch,
+                &change,
+            )
+            .await
+            .map_err(Self::err)?;
+        let settings = BranchProtectionSettings::from_api(&result);
+        self.respond(serde_json::json!(settings), &params.0.output)
+    }
 
-        if let Some(ref checks) = params.0.required_status_checks {
-            body["required_status_checks"] = serde_json::json!({
-                "strict": checks.strict.unwrap_or(false),
-                "contexts": checks.contexts.clone().unwrap_or_default()
-            });
-        } else {
-            body["required_status_checks"] = serde_json::Value::Null;
+    /// Apply branch protection to many repositories
+    #[tool(
+        description = "Roll out branch protection to many repositories at once: an explicit list, or an owner's repositories filtered by name glob and/or topic. By default only the given settings change (e.g. add_status_checks to require a new check); set replace to overwrite. Use dry_run to see each repository's changes first. Runs concurrently and returns a per-repository success/failure table."
+    )]
+    async fn apply_branch_protection_bulk(
+        &self,
+        params: Parameters<ApplyBranchProtectionBulkRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let account = params.0.account.as_deref();
+        let repos = match (&params.0.repos, &params.0.owner) {
+            (Some(repos), None) => repos.clone(),
+            (None, Some(owner)) => self
+                .gh
+                .find_repos(account, owner, params.0.name_glob.as_deref(), params.0.topic.as_deref())
+                .await
+                .map_err(Self::err)?,
+            _ => return Err(Self::err("provide either repos or owner")),
+        };
+        if repos.is_empty() {
+            return Err(Self::err("no repositories matched"));
         }
 
-        body["enforce_admins"] = serde_json::json!(params.0.enforce_admins.unwrap_or(false));
-
-        if let Some(ref reviews) = params.0.required_pull_request_reviews {
-            body["required_pull_request_reviews"] = serde_json::json!({
-                "required_approving_review_count": reviews.required_approving_review_count.unwrap_or(1),
-                "dismiss_stale_reviews": reviews.dismiss_stale_reviews.unwrap_or(false),
-                "require_code_owner_reviews": reviews.require_code_owner_reviews.unwrap_or(false)
-            });
+        let change = if params.0.replace.unwrap_or(false) {
+            ProtectionChange::Replace(params.0.settings.clone())
         } else {
-            body["required_pull_request_reviews"] = serde_json::Value::Null;
-        }
-
-        body["restrictions"] = serde_json::Value::Null;
-
-        if let Some(linear) = params.0.required_linear_history {
-            body["required_linear_history"] = serde_json::json!(linear);
-        }
-
-        if let Some(force) = params.0.allow_force_pushes {
-            body["allow_force_pushes"] = serde_json::json!(force);
-        }
-
-        if let Some(del) = params.0.allow_deletions {
-            body["allow_deletions"] = serde_json::json!(del);
-        }
-
-        // For simplified implementation, use -f flags for the basic case
-        // Full JSON body handling would require stdin piping
-        let _body_str = serde_json::to_string(&body).map_err(Self::err)?;
-        let enforce_admins_arg = format!("enforce_admins={}", params.0.enforce_admins.unwrap_or(false));
-
-        let result = self
+            ProtectionChange::Update {
+                settings: params.0.settings.clone(),
+                add_status_checks: params.0.add_status_checks.clone().unwrap_or_default(),
+                remove_status_checks: params.0.remove_status_checks.clone().unwrap_or_default(),
+            }
+        };
+        let results = self
             .gh
-            .run(
-                params.0.account.as_deref(),
-                &[
-                    "api",
-                    "-X",
-                    "PUT",
-                    &endpoint,
-                    "-H",
-                    "Accept: application/vnd.github+json",
-                    "-f",
-                    &enforce_admins_arg,
-                ],
+            .change_protection_bulk(
+                account,
+                repos,
+                params.0.branch.clone(),
+                change,
+                params.0.concurrency.unwrap_or(8),
+                params.0.dry_run.unwrap_or(false),
             )
             .await;
 
-        match result {
-            Ok(r) => Ok(CallToolResult::success(vec![Content::json(&r)?])),
-            Err(e) => Ok(CallToolResult::success(vec![Content::text(format!(
-                "Branch protection update attempted. Note: Full protection settings may require direct API access. Error details: {e}"
-            ))])),
-        }
+        let failed = results.iter().filter(|r| r.error.is_some()).count();
+        let result = serde_json::json!({
+            "succeeded": results.len() - failed,
+            "failed": failed,
+            "results": results,
+        });
+        self.respond(result, &params.0.output)
     }
 
     /// Remove branch protection from a branch
@@ -346,7 +892,9 @@ impl GitHubMcpServer {
     ) -> Result<CallToolResult, McpError> {
         let endpoint = format!(
             "repos/{}/{}/branches/{}/protection",
-            params.0.owner, params.0.repo, params.0.branch
+            params.0.owner,
+            params.0.repo,
+            encode_segment(&params.0.branch)
         );
         self.gh
             .api(params.0.account.as_deref(), &endpoint, Some("DELETE"), None)
@@ -359,45 +907,226 @@ impl GitHubMcpServer {
     }
 
     // ============================================
-    // Pull Request Tools
+    // Ruleset Tools
     // ============================================
 
-    /// Get details about a specific pull request
-    #[tool(description = "Get detailed information about a specific pull request.")]
-    async fn get_pr(&self, params: Parameters<GetPrRequest>) -> Result<CallToolResult, McpError> {
-        let repo = format!("{}/{}", params.0.owner, params.0.repo);
-        let number_str = params.0.number.to_string();
-        let args = vec![
-            "pr",
-            "view",
-            &number_str,
-            "--repo",
-            &repo,
-            "--json",
-            "number,title,state,body,author,createdAt,updatedAt,url,headRefName,baseRefName,mergeable,additions,deletions,changedFiles",
-        ];
+    /// List rulesets
+    #[tool(
+        description = "List rulesets for a repository (including ones inherited from its organization) or for an organization (omit repo)."
+    )]
+    async fn list_rulesets(&self, params: Parameters<ListRulesetsRequest>) -> Result<CallToolResult, McpError> {
+        let mut endpoint = format!("{}?per_page=100", params.0.scope.collection());
+        if params.0.scope.repo.is_some() {
+            endpoint.push_str("&includes_parents=true");
+        }
         let result = self
             .gh
-            .run(params.0.account.as_deref(), &args)
+            .api(params.0.account.as_deref(), &endpoint, None, None)
             .await
             .map_err(Self::err)?;
-        Ok(CallToolResult::success(vec![Content::json(&result)?]))
+        self.respond_compact(
+            result,
+            &params.0.output,
+            &["id", "name", "target", "enforcement", "source_type", "source"],
+        )
     }
 
-    /// Get the diff of a pull request
-    #[tool(description = "Get the diff/patch of a pull request.")]
-    async fn get_pr_diff(&self, params: Parameters<GetPrDiffRequest>) -> Result<CallToolResult, McpError> {
-        let repo = format!("{}/{}", params.0.owner, params.0.repo);
-        let number_str = params.0.number.to_string();
-        let args = vec!["pr", "diff", &number_str, "--repo", &repo];
+    /// Get a ruleset
+    #[tool(description = "Get a ruleset's conditions, rules and bypass actors by ID.")]
+    async fn get_ruleset(&self, params: Parameters<RulesetRequest>) -> Result<CallToolResult, McpError> {
+        let endpoint = format!("{}/{}", params.0.scope.collection(), params.0.ruleset_id);
+        let result = self
+            .gh
+            .api(params.0.account.as_deref(), &endpoint, None, None)
+            .await
+            .map_err(Self::err)?;
+        self.respond(result, &params.0.output)
+    }
+
+    /// Create a ruleset
+    #[tool(
+        description = "Create a ruleset on a repository or an organization (omit repo), with ref conditions, typed rules and bypass actors. Defaults to an active branch ruleset."
+    )]
+    async fn create_ruleset(&self, params: Parameters<CreateRulesetRequest>) -> Result<CallToolResult, McpError> {
+        if params.0.settings.name.is_none() {
+            return Err(Self::err("name is required to create a ruleset"));
+        }
+        let body = params.0.settings.create_body(params.0.scope.repo.is_none()).map_err(Self::err)?;
+
+        let result = self
+            .gh
+            .api_json(params.0.account.as_deref(), &params.0.scope.collection(), "POST", &body)
+            .await
+            .map_err(Self::err)?;
+        self.respond(result, &params.0.output)
+    }
+
+    /// Update a ruleset
+    #[tool(
+        description = "Update a ruleset. Only the given fields change; rules and bypass_actors replace the existing lists, and each given include or exclude list replaces that list while the other is kept."
+    )]
+    async fn update_ruleset(&self, params: Parameters<UpdateRulesetRequest>) -> Result<CallToolResult, McpError> {
+        let account = params.0.account.as_deref();
+        let endpoint = format!("{}/{}", params.0.scope.collection(), params.0.ruleset_id);
+        let current = self.gh.api(account, &endpoint, None, None).await.map_err(Self::err)?;
+        let body = params
+            .0
+            .settings
+            .update_body(params.0.scope.repo.is_none(), &current)
+            .map_err(Self::err)?;
+        let result = self
+            .gh
+            .api_json(account, &endpoint, "PUT", &body)
+            .await
+            .map_err(Self::err)?;
+        self.respond(result, &params.0.output)
+    }
+
+    /// Delete a ruleset
+    #[tool(description = "Delete a ruleset from a repository or an organization.")]
+    async fn delete_ruleset(&self, params: Parameters<RulesetRequest>) -> Result<CallToolResult, McpError> {
+        let endpoint = format!("{}/{}", params.0.scope.collection(), params.0.ruleset_id);
+        self.gh
+            .api(params.0.account.as_deref(), &endpoint, Some("DELETE"), None)
+            .await
+            .map_err(Self::err)?;
+        Ok(CallToolResult::success(vec![Content::text(format!(
+            "Ruleset {} deleted from {}",
+            params.0.ruleset_id, params.0.scope.owner
+        ))]))
+    }
+
+    /// Get the rules that apply to a branch
+    #[tool(
+        description = "Show the effective rules for a branch: every active rule from repository and organization rulesets that applies to it, with the ruleset each comes from."
+    )]
+    async fn get_rules_for_branch(&self, params: Parameters<GetRulesForBranchRequest>) -> Result<CallToolResult, McpError> {
+        let endpoint = format!(
+            "repos/{}/{}/rules/branches/{}?per_page=100",
+            params.0.owner,
+            params.0.repo,
+            encode_segment(&params.0.branch)
+        );
+        let result = self
+            .gh
+            .api(params.0.account.as_deref(), &endpoint, None, None)
+            .await
+            .map_err(Self::err)?;
+        self.respond_compact(
+            result,
+            &params.0.output,
+            &["type", "parameters", "ruleset_id", "ruleset_source", "ruleset_source_type"],
+        )
+    }
+
+    // ============================================
+    // Policy Tools
+    // ============================================
+
+    /// Diff repositories against a policy spec
+    #[tool(
+        description = "Compare repositories' live settings, branch protection, rulesets, labels, collaborators and teams with a declarative policy spec and report the drift. Changes nothing."
+    )]
+    async fn plan_policy(&self, Parameters(mut request): Parameters<PolicyRequest>) -> Result<CallToolResult, McpError> {
+        let spec = request.take_spec().map_err(Self::err)?;
+        let plans = self.gh.plan_policy(spec).await.map_err(Self::err)?;
+        self.respond(serde_json::json!(plans), &request.output)
+    }
+
+    /// Apply a policy spec to repositories
+    #[tool(
+        description = "Make repositories match a declarative policy spec, changing only what differs. Returns the changes made, with an error on any that failed."
+    )]
+    async fn apply_policy(&self, Parameters(mut request): Parameters<PolicyRequest>) -> Result<CallToolResult, McpError> {
+        let spec = request.take_spec().map_err(Self::err)?;
+        let plans = self.gh.plan_policy(spec).await.map_err(Self::err)?;
+        let applied = self.gh.apply_policy(plans).await;
+        self.respond(serde_json::json!(applied), &request.output)
+    }
+
+    // ============================================
+    // Pull Request Tools
+    // ============================================
+
+    /// Get details about a specific pull request
+    #[tool(description = "Get detailed information about a specific pull request.")]
+    async fn get_pr(&self, params: Parameters<GetPrRequest>) -> Result<CallToolResult, McpError> {
+        let repo = format!("{}/{}", params.0.owner, params.0.repo);
+        let number_str = params.0.number.to_string();
+        let args = vec![
+            "pr",
+            "view",
+            &number_str,
+            "--repo",
+            &repo,
+            "--json",
+            "number,title,state,body,author,createdAt,updatedAt,url,headRefName,baseRefName,mergeable,additions,deletions,changedFiles",
+        ];
         let result = self
             .gh
             .run(params.0.account.as_deref(), &args)
             .await
             .map_err(Self::err)?;
-        Ok(CallToolResult::success(vec![Content::text(
-            result.as_str().unwrap_or("").to_string(),
-        )]))
+        self.respond(result, &params.0.output)
+    }
+
+    /// Get the diff of a pull request
+    #[tool(
+        description = "Get the diff/patch of a pull request. Lockfiles and vendored/generated files are skipped by default. Use 'files' to fetch specific files or glob patterns, and 'hunk_offset' to page through large diffs hunk by hunk."
+    )]
+    async fn get_pr_diff(&self, params: Parameters<GetPrDiffRequest>) -> Result<CallToolResult, McpError> {
+        let repo = format!("{}/{}", params.0.owner, params.0.repo);
+        let number_str = params.0.number.to_string();
+        let args = vec!["pr", "diff", &number_str, "--repo", &repo];
+        let text = self
+            .gh
+            .run_raw(params.0.account.as_deref(), &args)
+            .await
+            .map_err(Self::err)?;
+
+        let include = params.0.files.as_deref().map(diff::glob_set).transpose().map_err(Self::err)?;
+        let ignore = if params.0.include_ignored.unwrap_or(false) {
+            Vec::new()
+        } else {
+            self.gh.config().diff.ignore.clone()
+        };
+        let ignore = diff::glob_set(&ignore).map_err(Self::err)?;
+        let selection = diff::select_files(diff::split_files(&text), include.as_ref(), &ignore);
+
+        let budget = self.gh.config().output.max_response_bytes;
+        let mut content = Vec::new();
+        if let Some(offset) = params.0.hunk_offset {
+            let page = diff::page_hunks(&selection.files, offset, budget);
+            content.push(Content::text(page.text));
+            if page.end < page.total {
+                content.push(Content::text(format!(
+                    "[Showing hunks {}-{} of {}. Continue with hunk_offset={}.]",
+                    page.start,
+                    page.end - 1,
+                    page.total,
+                    page.end
+                )));
+            }
+        } else {
+            let selected: String = selection.files.iter().map(|f| f.text).collect();
+            let (selected, omission) = output::fit_diff(&selected, budget);
+            content.push(Content::text(selected));
+            if let Some(omission) = omission {
+                content.push(Content::text(omission.footer(
+                    budget,
+                    "Pass 'files' to fetch specific files, or 'hunk_offset' to page through hunks.",
+                )));
+            }
+        }
+
+        if !selection.ignored.is_empty() {
+            content.push(Content::text(format!(
+                "[Skipped {} ignored files: {}. Pass include_ignored=true or list them in 'files' to include them.]",
+                selection.ignored.len(),
+                selection.ignored.join(", ")
+            )));
+        }
+        Ok(CallToolResult::success(content))
     }
 
     /// Get files changed in a pull request
@@ -411,7 +1140,29 @@ impl GitHubMcpServer {
             .run(params.0.account.as_deref(), &args)
             .await
             .map_err(Self::err)?;
-        Ok(CallToolResult::success(vec![Content::json(&result)?]))
+        self.respond(result, &params.0.output)
+    }
+
+    /// Get a compact overview of a pull request in a single GraphQL request
+    #[tool(
+        description = "Get a compact overview of a pull request in one request: title, state, author, branches, mergeability, size, plus body, labels, reviewers and review states, check rollup, changed files, linked issues and unresolved review thread count. Use 'sections' to limit output. Prefer this over separate get_pr/get_pr_files/checks calls."
+    )]
+    async fn get_pr_overview(&self, params: Parameters<GetPrOverviewRequest>) -> Result<CallToolResult, McpError> {
+        let sections = params.0.sections.as_deref().unwrap_or(&PrOverviewSection::ALL);
+        let query = pr_overview_query(sections);
+        let variables = serde_json::json!({
+            "owner": params.0.owner,
+            "repo": params.0.repo,
+            "number": params.0.number,
+        });
+
+        let data = self
+            .gh
+            .graphql(params.0.account.as_deref(), &query, &variables)
+            .await
+            .map_err(Self::err)?;
+        let overview = shape_pr_overview(&data["repository"]["pullRequest"]);
+        self.respond(overview, &params.0.output)
     }
 
     /// List pull requests in a repository
@@ -453,30 +1204,26 @@ impl GitHubMcpServer {
             .run(params.0.account.as_deref(), &args)
             .await
             .map_err(Self::err)?;
-        Ok(CallToolResult::success(vec![Content::json(&result)?]))
+        self.respond(result, &params.0.output)
     }
 
     /// Search pull requests
     #[tool(description = "Search pull requests using GitHub search syntax.")]
     async fn search_prs(&self, params: Parameters<SearchPrsRequest>) -> Result<CallToolResult, McpError> {
-        let mut args = vec!["search", "prs", &params.0.query];
-
-        let limit_str;
-        if let Some(limit) = params.0.limit {
-            limit_str = limit.to_string();
-            args.push("--limit");
-            args.push(&limit_str);
+        if params.0.all_accounts.unwrap_or(false) {
+            return self
+                .search_all_accounts(SearchKind::Prs, &params.0.query, params.0.limit, &params.0.output)
+                .await;
         }
 
-        args.push("--json");
-        args.push("number,title,state,author,repository,createdAt,updatedAt,url");
-
+        let args = SearchKind::Prs.args(&params.0.query, params.0.limit);
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
         let result = self
             .gh
             .run(params.0.account.as_deref(), &args)
             .await
             .map_err(Self::err)?;
-        Ok(CallToolResult::success(vec![Content::json(&result)?]))
+        self.respond(result, &params.0.output)
     }
 
     /// Create a new pull request
@@ -515,7 +1262,7 @@ impl GitHubMcpServer {
             .run(params.0.account.as_deref(), &args)
             .await
             .map_err(Self::err)?;
-        Ok(CallToolResult::success(vec![Content::json(&result)?]))
+        self.respond(result, &params.0.output)
     }
 
     /// Edit an existing pull request
@@ -548,7 +1295,7 @@ impl GitHubMcpServer {
             .run(params.0.account.as_deref(), &args)
             .await
             .map_err(Self::err)?;
-        Ok(CallToolResult::success(vec![Content::json(&result)?]))
+        self.respond(result, &params.0.output)
     }
 
     /// Merge a pull request
@@ -579,7 +1326,7 @@ impl GitHubMcpServer {
             .run(params.0.account.as_deref(), &args)
             .await
             .map_err(Self::err)?;
-        Ok(CallToolResult::success(vec![Content::json(&result)?]))
+        self.respond(result, &params.0.output)
     }
 
     /// Close a pull request without merging
@@ -593,7 +1340,7 @@ impl GitHubMcpServer {
             .run(params.0.account.as_deref(), &args)
             .await
             .map_err(Self::err)?;
-        Ok(CallToolResult::success(vec![Content::json(&result)?]))
+        self.respond(result, &params.0.output)
     }
 
     /// Add a comment to a pull request
@@ -607,7 +1354,7 @@ impl GitHubMcpServer {
             .run(params.0.account.as_deref(), &args)
             .await
             .map_err(Self::err)?;
-        Ok(CallToolResult::success(vec![Content::json(&result)?]))
+        self.respond(result, &params.0.output)
     }
 
     // ============================================
@@ -635,34 +1382,188 @@ impl GitHubMcpServer {
             if let Ok(bytes) = base64::Engine::decode(&base64::engine::general_purpose::STANDARD, &decoded)
                 && let Ok(text) = String::from_utf8(bytes)
             {
-                return Ok(CallToolResult::success(vec![Content::text(text)]));
+                let budget = self.gh.config().output.max_response_bytes;
+                let (text, omission) = output::fit_text(&text, budget);
+                let mut content = vec![Content::text(text)];
+                if let Some(omission) = omission {
+                    content.push(Content::text(omission.footer(budget, GET_FILE_HINT)));
+                }
+                return Ok(CallToolResult::success(content));
             }
         }
 
-        Ok(CallToolResult::success(vec![Content::json(&result)?]))
+        self.respond(result, &params.0.output)
     }
 
     /// Search code across repositories
     #[tool(description = "Search code using GitHub code search syntax.")]
     async fn search_code(&self, params: Parameters<SearchCodeRequest>) -> Result<CallToolResult, McpError> {
-        let mut args = vec!["search", "code", &params.0.query];
+        if params.0.all_accounts.unwrap_or(false) {
+            return self
+                .search_all_accounts(SearchKind::Code, &params.0.query, params.0.limit, &params.0.output)
+                .await;
+        }
 
-        let limit_str;
-        if let Some(limit) = params.0.limit {
-            limit_str = limit.to_string();
-            args.push("--limit");
-            args.push(&limit_str);
+        let args = SearchKind::Code.args(&params.0.query, params.0.limit);
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        let result = self
+            .gh
+            .run(params.0.account.as_deref(), &args)
+            .await
+            .map_err(Self::err)?;
+        self.respond_with(result, &params.0.output, SEARCH_CODE_FIELDS, SEARCH_CODE_HINT)
+    }
+
+    /// Search under every account
+    #[tool(
+        description = "Search PRs, issues, code or repos under every configured account at once. Results are merged by URL; each hit lists the accounts that can see it, and accounts whose search failed are reported under 'errors'."
+    )]
+    async fn search_everywhere(&self, params: Parameters<SearchEverywhereRequest>) -> Result<CallToolResult, McpError> {
+        let kind = params.0.kind.unwrap_or_default();
+        self.search_all_accounts(kind, &params.0.query, params.0.limit, &params.0.output)
+            .await
+    }
+
+    /// Get a single commit
+    #[tool(
+        description = "Get a commit: full message, author and committer, signature verification, parents, changed files with stats, the pull requests associated with it and its combined status. Set include_patch for per-file patches."
+    )]
+    async fn get_commit(&self, params: Parameters<GetCommitRequest>) -> Result<CallToolResult, McpError> {
+        let account = params.0.account.as_deref();
+        let endpoint = format!("repos/{}/{}/commits/{}", params.0.owner, params.0.repo, params.0.sha);
+        let pulls_endpoint = format!("{endpoint}/pulls");
+        let status_endpoint = format!("{endpoint}/status");
+
+        let (commit, pulls, status) = tokio::join!(
+            self.gh.api(account, &endpoint, None, None),
+            self.gh.api(account, &pulls_endpoint, None, None),
+            self.gh.api(account, &status_endpoint, None, None),
+        );
+        let commit = commit.map_err(Self::err)?;
+        let pulls = pulls.map_err(Self::err)?;
+        let status = status.map_err(Self::err)?;
+
+        let result = shape_commit(&commit, &pulls, &status, params.0.include_patch.unwrap_or(false));
+        self.respond(result, &params.0.output)
+    }
+
+    /// Get the commit history of a file, following renames
+    #[tool(
+        description = "List commits that touched a file, newest first. Follows renames to the file's previous paths; each commit reports the path the file had at that point."
+    )]
+    async fn get_file_history(&self, params: Parameters<GetFileHistoryRequest>) -> Result<CallToolResult, McpError> {
+        // Bound the number of renames followed so a pathological history can't loop forever
+        const MAX_RENAMES: usize = 10;
+
+        let account = params.0.account.as_deref();
+        let repo_endpoint = format!("repos/{}/{}", params.0.owner, params.0.repo);
+        let limit = params.0.limit.unwrap_or(30) as usize;
+        let follow = params.0.follow_renames.unwrap_or(true);
+
+        let mut history = Vec::new();
+        let mut path = params.0.path.clone();
+        let mut sha = params.0.sha.clone();
+        // GitHub caps per_page at 100, so longer histories take several pages per path
+        let per_page = limit.clamp(1, 100);
+        for _ in 0..=MAX_RENAMES {
+            let mut exhausted = false;
+            let mut oldest = None;
+            for page in 1.. {
+                if history.len() >= limit {
+                    break;
+                }
+                let mut endpoint = format!(
+                    "{repo_endpoint}/commits?path={}&per_page={per_page}&page={page}",
+                    encode_segment(&path)
+                );
+                if let Some(ref sha) = sha {
+                    endpoint.push_str(&format!("&sha={}", encode_segment(sha)));
+                }
+                let commits = self.gh.api(account, &endpoint, None, None).await.map_err(Self::err)?;
+                let commits = commits.as_array().cloned().unwrap_or_default();
+
+                // Only a short page shows this path's history is complete
+                exhausted = commits.len() < per_page;
+                oldest = commits.last().and_then(|c| c["sha"].as_str()).map(String::from).or(oldest);
+                for commit in commits.into_iter().take(limit - history.len()) {
+                    history.push(serde_json::json!({
+                        "sha": commit["sha"],
+                        "message": commit["commit"]["message"].as_str().and_then(|m| m.lines().next()),
+                        "author": commit["author"]["login"].as_str().or(commit["commit"]["author"]["name"].as_str()),
+                        "date": commit["commit"]["author"]["date"],
+                        "path": path,
+                    }));
+                }
+                if exhausted {
+                    break;
+                }
+            }
+
+            // The oldest commit for this path may be the one that renamed the file into place
+            let Some(oldest) = oldest.filter(|_| follow && exhausted && history.len() < limit) else {
+                break;
+            };
+            let details = self
+                .gh
+                .api(account, &format!("{repo_endpoint}/commits/{oldest}"), None, None)
+                .await
+                .map_err(Self::err)?;
+            let (Some(previous), Some(parent)) = (renamed_from(&details, &path), details["parents"][0]["sha"].as_str())
+            else {
+                break;
+            };
+            debug!("Following rename of {} from {} at {}", path, previous, oldest);
+            path = previous;
+            sha = Some(parent.to_string());
         }
 
-        args.push("--json");
-        args.push("path,repository,textMatches");
+        self.respond(Value::Array(history), &params.0.output)
+    }
+
+    /// Blame a file
+    #[tool(
+        description = "Blame a file: line ranges with the commit SHA, author, date and message that last changed them. Use start_line/end_line to blame part of a file."
+    )]
+    async fn blame_file(&self, params: Parameters<BlameFileRequest>) -> Result<CallToolResult, McpError> {
+        let variables = serde_json::json!({
+            "owner": params.0.owner,
+            "repo": params.0.repo,
+            "expression": params.0.r#ref.as_deref().unwrap_or("HEAD"),
+            "path": params.0.path,
+        });
+        let data = self
+            .gh
+            .graphql(params.0.account.as_deref(), BLAME_QUERY, &variables)
+            .await
+            .map_err(Self::err)?;
+
+        let ranges = &data["repository"]["object"]["blame"]["ranges"];
+        if !ranges.is_array() {
+            return Err(Self::err(format!(
+                "ref '{}' not found",
+                params.0.r#ref.as_deref().unwrap_or("HEAD")
+            )));
+        }
+        let blame = shape_blame(ranges, params.0.start_line, params.0.end_line);
+        self.respond(Value::Array(blame), &params.0.output)
+    }
 
+    /// Compare two refs
+    #[tool(
+        description = "Compare two refs (base...head): ahead/behind counts, commits in head not in base, and changed files with stats. Use it to check whether a branch has diverged before opening a PR, or whether a tag includes a commit. Set include_patch for per-file patches. The API lists at most 250 commits and 300 files."
+    )]
+    async fn compare_refs(&self, params: Parameters<CompareRefsRequest>) -> Result<CallToolResult, McpError> {
+        let endpoint = format!(
+            "repos/{}/{}/compare/{}...{}",
+            params.0.owner, params.0.repo, params.0.base, params.0.head
+        );
         let result = self
             .gh
-            .run(params.0.account.as_deref(), &args)
+            .api(params.0.account.as_deref(), &endpoint, None, None)
             .await
             .map_err(Self::err)?;
-        Ok(CallToolResult::success(vec![Content::json(&result)?]))
+        let comparison = shape_comparison(&result, params.0.include_patch.unwrap_or(false));
+        self.respond(comparison, &params.0.output)
     }
 
     /// List commits in a repository
@@ -697,7 +1598,11 @@ impl GitHubMcpServer {
             .api(params.0.account.as_deref(), &endpoint, None, None)
             .await
             .map_err(Self::err)?;
-        Ok(CallToolResult::success(vec![Content::json(&result)?]))
+        self.respond_compact(
+            result,
+            &params.0.output,
+            &["sha", "commit.message", "commit.author.name", "commit.author.date", "author.login"],
+        )
     }
 
     // ============================================
@@ -717,12 +1622,15 @@ impl GitHubMcpServer {
             args.push(&limit_str);
         }
 
+        args.push("--json");
+        args.push("tagName,name,isDraft,isPrerelease,isLatest,publishedAt");
+
         let result = self
             .gh
             .run(params.0.account.as_deref(), &args)
             .await
             .map_err(Self::err)?;
-        Ok(CallToolResult::success(vec![Content::json(&result)?]))
+        self.respond(result, &params.0.output)
     }
 
     /// Get details about a specific release
@@ -743,7 +1651,7 @@ impl GitHubMcpServer {
             .run(params.0.account.as_deref(), &args)
             .await
             .map_err(Self::err)?;
-        Ok(CallToolResult::success(vec![Content::json(&result)?]))
+        self.respond(result, &params.0.output)
     }
 
     /// Create a new release
@@ -787,7 +1695,7 @@ impl GitHubMcpServer {
             .run(params.0.account.as_deref(), &args)
             .await
             .map_err(Self::err)?;
-        Ok(CallToolResult::success(vec![Content::json(&result)?]))
+        self.respond(result, &params.0.output)
     }
 
     /// Delete a release
@@ -805,7 +1713,7 @@ impl GitHubMcpServer {
             .run(params.0.account.as_deref(), &args)
             .await
             .map_err(Self::err)?;
-        Ok(CallToolResult::success(vec![Content::json(&result)?]))
+        self.respond(result, &params.0.output)
     }
 
     /// List assets in a release
@@ -821,7 +1729,11 @@ impl GitHubMcpServer {
             .run(params.0.account.as_deref(), &args)
             .await
             .map_err(Self::err)?;
-        Ok(CallToolResult::success(vec![Content::json(&result)?]))
+        self.respond_compact(
+            result,
+            &params.0.output,
+            &["assets.name", "assets.size", "assets.downloadCount", "assets.url"],
+        )
     }
 
     /// Download release assets
@@ -850,7 +1762,7 @@ impl GitHubMcpServer {
             .run(params.0.account.as_deref(), &args)
             .await
             .map_err(Self::err)?;
-        Ok(CallToolResult::success(vec![Content::json(&result)?]))
+        self.respond(result, &params.0.output)
     }
 
     // ============================================
@@ -869,7 +1781,7 @@ impl GitHubMcpServer {
             .api(params.0.account.as_deref(), &endpoint, None, None)
             .await
             .map_err(Self::err)?;
-        Ok(CallToolResult::success(vec![Content::json(&result)?]))
+        self.respond_compact(result, &params.0.output, &["name", "commit.sha"])
     }
 
     /// Create a new tag
@@ -912,7 +1824,7 @@ impl GitHubMcpServer {
             .run(params.0.account.as_deref(), &args)
             .await
             .map_err(Self::err)?;
-        Ok(CallToolResult::success(vec![Content::json(&result)?]))
+        self.respond(result, &params.0.output)
     }
 
     /// Delete a tag
@@ -978,7 +1890,7 @@ impl GitHubMcpServer {
             .run(params.0.account.as_deref(), &args)
             .await
             .map_err(Self::err)?;
-        Ok(CallToolResult::success(vec![Content::json(&result)?]))
+        self.respond(result, &params.0.output)
     }
 
     /// List artifacts from a workflow run
@@ -996,7 +1908,11 @@ impl GitHubMcpServer {
             .api(params.0.account.as_deref(), &endpoint, None, None)
             .await
             .map_err(Self::err)?;
-        Ok(CallToolResult::success(vec![Content::json(&result)?]))
+        self.respond_compact(
+            result,
+            &params.0.output,
+            &["total_count", "artifacts.id", "artifacts.name", "artifacts.size_in_bytes", "artifacts.expired", "artifacts.created_at"],
+        )
     }
 
     /// Download artifacts from a workflow run
@@ -1026,104 +1942,1166 @@ impl GitHubMcpServer {
             .run(params.0.account.as_deref(), &args)
             .await
             .map_err(Self::err)?;
-        Ok(CallToolResult::success(vec![Content::json(&result)?]))
+        self.respond(result, &params.0.output)
     }
 
     // ============================================
-    // Team and Collaborator Tools
+    // Environment and Deployment Tools
     // ============================================
 
-    /// List collaborators on a repository
-    #[tool(description = "List collaborators on a repository.")]
-    async fn list_collaborators(
-        &self,
-        params: Parameters<ListCollaboratorsRequest>,
-    ) -> Result<CallToolResult, McpError> {
-        let mut endpoint = format!("repos/{}/{}/collaborators", params.0.owner, params.0.repo);
-
-        if let Some(ref affiliation) = params.0.affiliation {
-            endpoint.push_str(&format!("?affiliation={affiliation}"));
-        }
-
+    /// List deployment environments
+    #[tool(description = "List a repository's deployment environments with their protection rules and branch policies.")]
+    async fn list_environments(&self, params: Parameters<ListEnvironmentsRequest>) -> Result<CallToolResult, McpError> {
+        let endpoint = format!("repos/{}/{}/environments?per_page=100", params.0.owner, params.0.repo);
         let result = self
             .gh
             .api(params.0.account.as_deref(), &endpoint, None, None)
             .await
             .map_err(Self::err)?;
-        Ok(CallToolResult::success(vec![Content::json(&result)?]))
+        self.respond_compact(
+            result,
+            &params.0.output,
+            &[
+                "total_count",
+                "environments.name",
+                "environments.protection_rules",
+                "environments.deployment_branch_policy",
+            ],
+        )
     }
 
-    /// Add a collaborator to a repository
-    #[tool(description = "Add a collaborator to a repository with specified permission level.")]
-    async fn add_collaborator(&self, params: Parameters<AddCollaboratorRequest>) -> Result<CallToolResult, McpError> {
+    /// Create or update a deployment environment
+    #[tool(
+        description = "Create or update a deployment environment: wait timer, required reviewers (users or teams), self-review prevention and which branches can deploy. With branch_patterns, the environment's custom branch policies are made to match exactly."
+    )]
+    async fn set_environment(&self, params: Parameters<SetEnvironmentRequest>) -> Result<CallToolResult, McpError> {
+        let account = params.0.account.as_deref();
+        let owner = &params.0.owner;
         let endpoint = format!(
-            "repos/{}/{}/collaborators/{}",
-            params.0.owner, params.0.repo, params.0.username
+            "repos/{owner}/{}/environments/{}",
+            params.0.repo,
+            encode_segment(&params.0.environment)
         );
 
-        let permission = params.0.permission.as_deref().unwrap_or("push");
-        let perm_arg = format!("permission={permission}");
-        let args = vec!["api", "-X", "PUT", &endpoint, "-f", &perm_arg];
+        let mut reviewers = Vec::new();
+        for reviewer in params.0.reviewers.iter().flatten() {
+            let (kind, lookup) = match reviewer {
+                EnvironmentReviewer::User(login) => ("User", format!("users/{login}")),
+                EnvironmentReviewer::Team(slug) => ("Team", format!("orgs/{owner}/teams/{slug}")),
+            };
+            let found = self.gh.api(account, &lookup, None, None).await.map_err(Self::err)?;
+            let id = found["id"]
+                .as_u64()
+                .ok_or_else(|| Self::err(format!("no id returned for {lookup}")))?;
+            reviewers.push((kind, id));
+        }
 
-        let result = self
+        let body = params
+            .0
+            .to_body(params.0.reviewers.as_ref().map(|_| reviewers.as_slice()))
+            .map_err(Self::err)?;
+        let mut result = self
             .gh
-            .run(params.0.account.as_deref(), &args)
+            .api_json(account, &endpoint, "PUT", &body)
             .await
             .map_err(Self::err)?;
-        Ok(CallToolResult::success(vec![Content::json(&result)?]))
+
+        if let Some(ref patterns) = params.0.branch_patterns {
+            let policies_endpoint = format!("{endpoint}/deployment-branch-policies");
+            let existing = self
+                .gh
+                .api(account, &format!("{policies_endpoint}?per_page=100"), None, None)
+                .await
+                .map_err(Self::err)?;
+            let existing = existing["branch_policies"].as_array().cloned().unwrap_or_default();
+
+            for policy in &existing {
+                let stale = policy["name"].as_str().is_some_and(|name| !patterns.iter().any(|p| p == name));
+                if let (true, Some(id)) = (stale, policy["id"].as_u64()) {
+                    self.gh
+                        .api(account, &format!("{policies_endpoint}/{id}"), Some("DELETE"), None)
+                        .await
+                        .map_err(Self::err)?;
+                }
+            }
+            for pattern in patterns {
+                if existing.iter().any(|policy| policy["name"] == pattern.as_str()) {
+                    continue;
+                }
+                let body = serde_json::json!({ "name": pattern, "type": "branch" });
+                self.gh
+                    .api_json(account, &policies_endpoint, "POST", &body)
+                    .await
+                    .map_err(Self::err)?;
+            }
+            result["branch_patterns"] = serde_json::json!(patterns);
+        }
+
+        self.respond_compact(
+            result,
+            &params.0.output,
+            &["name", "html_url", "protection_rules", "deployment_branch_policy", "branch_patterns"],
+        )
     }
 
-    /// Remove a collaborator from a repository
-    #[tool(description = "Remove a collaborator from a repository.")]
-    async fn remove_collaborator(
-        &self,
-        params: Parameters<RemoveCollaboratorRequest>,
-    ) -> Result<CallToolResult, McpError> {
+    /// Delete a deployment environment
+    #[tool(description = "Delete a deployment environment, including its secrets, variables and protection rules.")]
+    async fn delete_environment(&self, params: Parameters<DeleteEnvironmentRequest>) -> Result<CallToolResult, McpError> {
         let endpoint = format!(
-            "repos/{}/{}/collaborators/{}",
-            params.0.owner, params.0.repo, params.0.username
+            "repos/{}/{}/environments/{}",
+            params.0.owner,
+            params.0.repo,
+            encode_segment(&params.0.environment)
         );
-
         self.gh
             .api(params.0.account.as_deref(), &endpoint, Some("DELETE"), None)
             .await
             .map_err(Self::err)?;
         Ok(CallToolResult::success(vec![Content::text(format!(
-            "Collaborator '{}' removed successfully",
-            params.0.username
+            "Environment '{}' deleted from {}/{}",
+            params.0.environment, params.0.owner, params.0.repo
         ))]))
     }
 
-    /// List teams in an organization
-    #[tool(description = "List teams in an organization.")]
-    async fn list_teams(&self, params: Parameters<ListTeamsRequest>) -> Result<CallToolResult, McpError> {
-        let mut endpoint = format!("orgs/{}/teams", params.0.org);
-        if let Some(limit) = params.0.limit {
-            endpoint.push_str(&format!("?per_page={limit}"));
+    /// List deployments
+    #[tool(description = "List deployments in a repository, optionally filtered by environment or ref. Newest first.")]
+    async fn list_deployments(&self, params: Parameters<ListDeploymentsRequest>) -> Result<CallToolResult, McpError> {
+        let mut endpoint = format!(
+            "repos/{}/{}/deployments?per_page={}",
+            params.0.owner,
+            params.0.repo,
+            params.0.limit.unwrap_or(30).min(100)
+        );
+        if let Some(ref environment) = params.0.environment {
+            endpoint.push_str(&format!("&environment={}", encode_segment(environment)));
+        }
+        if let Some(ref r) = params.0.r#ref {
+            endpoint.push_str(&format!("&ref={}", encode_segment(r)));
         }
+
         let result = self
             .gh
             .api(params.0.account.as_deref(), &endpoint, None, None)
             .await
             .map_err(Self::err)?;
-        Ok(CallToolResult::success(vec![Content::json(&result)?]))
+        self.respond_compact(
+            result,
+            &params.0.output,
+            &["id", "ref", "sha", "environment", "description", "creator.login", "created_at"],
+        )
     }
 
-    /// Get members of a team
-    #[tool(description = "Get members of a team in an organization.")]
-    async fn get_team_members(&self, params: Parameters<GetTeamMembersRequest>) -> Result<CallToolResult, McpError> {
-        let mut endpoint = format!("orgs/{}/teams/{}/members", params.0.org, params.0.team);
+    /// Create a deployment
+    #[tool(
+        description = "Create a deployment of a ref to an environment. Deployment tooling picks it up via the deployment event; report progress with create_deployment_status."
+    )]
+    async fn create_deployment(&self, params: Parameters<CreateDeploymentRequest>) -> Result<CallToolResult, McpError> {
+        let endpoint = format!("repos/{}/{}/deployments", params.0.owner, params.0.repo);
+        let result = self
+            .gh
+            .api_json(params.0.account.as_deref(), &endpoint, "POST", &params.0.to_body())
+            .await
+            .map_err(Self::err)?;
+        self.respond_compact(
+            result,
+            &params.0.output,
+            &["id", "ref", "sha", "environment", "description", "created_at", "message"],
+        )
+    }
 
-        if let Some(ref role) = params.0.role {
-            endpoint.push_str(&format!("?role={role}"));
+    /// List deployment statuses
+    #[tool(description = "List the statuses reported for a deployment, newest first.")]
+    async fn list_deployment_statuses(
+        &self,
+        params: Parameters<ListDeploymentStatusesRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let endpoint = format!(
+            "repos/{}/{}/deployments/{}/statuses?per_page=100",
+            params.0.owner, params.0.repo, params.0.deployment_id
+        );
+        let result = self
+            .gh
+            .api(params.0.account.as_deref(), &endpoint, None, None)
+            .await
+            .map_err(Self::err)?;
+        self.respond_compact(
+            result,
+            &params.0.output,
+            &["id", "state", "description", "environment_url", "log_url", "creator.login", "created_at"],
+        )
+    }
+
+    /// Create a deployment status
+    #[tool(description = "Report a deployment's status (queued, in_progress, success, failure, error, inactive).")]
+    async fn create_deployment_status(
+        &self,
+        params: Parameters<CreateDeploymentStatusRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let endpoint = format!(
+            "repos/{}/{}/deployments/{}/statuses",
+            params.0.owner, params.0.repo, params.0.deployment_id
+        );
+        let mut body = serde_json::json!({ "state": params.0.state });
+        for (key, value) in [
+            ("description", &params.0.description),
+            ("environment_url", &params.0.environment_url),
+            ("log_url", &params.0.log_url),
+        ] {
+            if let Some(value) = value {
+                body[key] = serde_json::json!(value);
+            }
+        }
+        if let Some(auto_inactive) = params.0.auto_inactive {
+            body["auto_inactive"] = serde_json::json!(auto_inactive);
         }
 
         let result = self
             .gh
-            .api(params.0.account.as_deref(), &endpoint, None, None)
+            .api_json(params.0.account.as_deref(), &endpoint, "POST", &body)
             .await
             .map_err(Self::err)?;
-        Ok(CallToolResult::success(vec![Content::json(&result)?]))
+        self.respond_compact(
+            result,
+            &params.0.output,
+            &["id", "state", "description", "environment", "environment_url", "log_url", "created_at"],
+        )
+    }
+
+    /// Approve or reject a workflow run's pending deployments
+    #[tool(
+        description = "Approve (or reject) a workflow run that is waiting on environment review. Reviews every pending environment you can approve unless specific environments are named."
+    )]
+    async fn approve_pending_deployment(
+        &self,
+        params: Parameters<ApprovePendingDeploymentRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let account = params.0.account.as_deref();
+        let endpoint = format!(
+            "repos/{}/{}/actions/runs/{}/pending_deployments",
+            params.0.owner, params.0.repo, params.0.run_id
+        );
+
+        let pending = self.gh.api(account, &endpoint, None, None).await.map_err(Self::err)?;
+        let environment_ids =
+            select_pending_environments(&pending, params.0.environments.as_deref()).map_err(Self::err)?;
+
+        let state = if params.0.reject.unwrap_or(false) { "rejected" } else { "approved" };
+        let body = serde_json::json!({
+            "environment_ids": environment_ids,
+            "state": state,
+            "comment": params.0.comment.as_deref().unwrap_or_default(),
+        });
+        let result = self
+            .gh
+            .api_json(account, &endpoint, "POST", &body)
+            .await
+            .map_err(Self::err)?;
+        self.respond_compact(
+            result,
+            &params.0.output,
+            &["id", "ref", "environment", "description", "created_at"],
+        )
+    }
+
+    // ============================================
+    // Team and Collaborator Tools
+    // ============================================
+
+    /// List collaborators on a repository
+    #[tool(description = "List collaborators on a repository.")]
+    async fn list_collaborators(
+        &self,
+        params: Parameters<ListCollaboratorsRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let mut endpoint = format!("repos/{}/{}/collaborators", params.0.owner, params.0.repo);
+
+        if let Some(ref affiliation) = params.0.affiliation {
+            endpoint.push_str(&format!("?affiliation={affiliation}"));
+        }
+
+        let result = self
+            .gh
+            .api(params.0.account.as_deref(), &endpoint, None, None)
+            .await
+            .map_err(Self::err)?;
+        self.respond_compact(result, &params.0.output, &["login", "role_name"])
+    }
+
+    /// Add a collaborator to a repository
+    #[tool(description = "Add a collaborator to a repository with specified permission level.")]
+    async fn add_collaborator(&self, params: Parameters<AddCollaboratorRequest>) -> Result<CallToolResult, McpError> {
+        let endpoint = format!(
+            "repos/{}/{}/collaborators/{}",
+            params.0.owner, params.0.repo, params.0.username
+        );
+
+        let permission = params.0.permission.as_deref().unwrap_or("push");
+        let perm_arg = format!("permission={permission}");
+        let args = vec!["api", "-X", "PUT", &endpoint, "-f", &perm_arg];
+
+        let result = self
+            .gh
+            .run(params.0.account.as_deref(), &args)
+            .await
+            .map_err(Self::err)?;
+        self.respond(result, &params.0.output)
+    }
+
+    /// Remove a collaborator from a repository
+    #[tool(description = "Remove a collaborator from a repository.")]
+    async fn remove_collaborator(
+        &self,
+        params: Parameters<RemoveCollaboratorRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let endpoint = format!(
+            "repos/{}/{}/collaborators/{}",
+            params.0.owner, params.0.repo, params.0.username
+        );
+
+        self.gh
+            .api(params.0.account.as_deref(), &endpoint, Some("DELETE"), None)
+            .await
+            .map_err(Self::err)?;
+        Ok(CallToolResult::success(vec![Content::text(format!(
+            "Collaborator '{}' removed successfully",
+            params.0.username
+        ))]))
+    }
+
+    /// List teams in an organization
+    #[tool(description = "List teams in an organization.")]
+    async fn list_teams(&self, params: Parameters<ListTeamsRequest>) -> Result<CallToolResult, McpError> {
+        let mut endpoint = format!("orgs/{}/teams", params.0.org);
+        if let Some(limit) = params.0.limit {
+            endpoint.push_str(&format!("?per_page={limit}"));
+        }
+        let result = self
+            .gh
+            .api(params.0.account.as_deref(), &endpoint, None, None)
+            .await
+            .map_err(Self::err)?;
+        self.respond_compact(result, &params.0.output, &["slug", "name", "description", "privacy", "permission"])
+    }
+
+    /// Get members of a team
+    #[tool(description = "Get members of a team in an organization.")]
+    async fn get_team_members(&self, params: Parameters<GetTeamMembersRequest>) -> Result<CallToolResult, McpError> {
+        let mut endpoint = format!("orgs/{}/teams/{}/members", params.0.org, params.0.team);
+
+        if let Some(ref role) = params.0.role {
+            endpoint.push_str(&format!("?role={role}"));
+        }
+
+        let result = self
+            .gh
+            .api(params.0.account.as_deref(), &endpoint, None, None)
+            .await
+            .map_err(Self::err)?;
+        self.respond_compact(result, &params.0.output, &["login"])
+    }
+    // ============================================
+    // Label and Milestone Tools
+    // ============================================
+
+    /// List labels in a repository
+    #[tool(description = "List labels in a repository.")]
+    async fn list_labels(&self, params: Parameters<ListLabelsRequest>) -> Result<CallToolResult, McpError> {
+        let endpoint = format!("repos/{}/{}/labels?per_page=100", params.0.owner, params.0.repo);
+        let result = self
+            .gh
+            .api(params.0.account.as_deref(), &endpoint, None, None)
+            .await
+            .map_err(Self::err)?;
+        self.respond_compact(result, &params.0.output, &["name", "color", "description"])
+    }
+
+    /// Create a label
+    #[tool(description = "Create a label in a repository.")]
+    async fn create_label(&self, params: Parameters<CreateLabelRequest>) -> Result<CallToolResult, McpError> {
+        let endpoint = format!("repos/{}/{}/labels", params.0.owner, params.0.repo);
+        let mut body = serde_json::json!({
+            "name": params.0.name,
+            "color": params.0.color.trim_start_matches('#'),
+        });
+        if let Some(ref description) = params.0.description {
+            body["description"] = Value::String(description.clone());
+        }
+
+        let result = self
+            .gh
+            .api_json(params.0.account.as_deref(), &endpoint, "POST", &body)
+            .await
+            .map_err(Self::err)?;
+        self.respond(result, &params.0.output)
+    }
+
+    /// Update a label
+    #[tool(description = "Update a label's name, color or description.")]
+    async fn update_label(&self, params: Parameters<UpdateLabelRequest>) -> Result<CallToolResult, McpError> {
+        let endpoint = format!(
+            "repos/{}/{}/labels/{}",
+            params.0.owner,
+            params.0.repo,
+            encode_segment(&params.0.name)
+        );
+        let mut body = serde_json::json!({});
+        if let Some(ref new_name) = params.0.new_name {
+            body["new_name"] = Value::String(new_name.clone());
+        }
+        if let Some(ref color) = params.0.color {
+            body["color"] = Value::String(color.trim_start_matches('#').to_string());
+        }
+        if let Some(ref description) = params.0.description {
+            body["description"] = Value::String(description.clone());
+        }
+
+        let result = self
+            .gh
+            .api_json(params.0.account.as_deref(), &endpoint, "PATCH", &body)
+            .await
+            .map_err(Self::err)?;
+        self.respond(result, &params.0.output)
+    }
+
+    /// Delete a label
+    #[tool(description = "Delete a label from a repository. It is removed from all issues and pull requests.")]
+    async fn delete_label(&self, params: Parameters<DeleteLabelRequest>) -> Result<CallToolResult, McpError> {
+        let endpoint = format!(
+            "repos/{}/{}/labels/{}",
+            params.0.owner,
+            params.0.repo,
+            encode_segment(&params.0.name)
+        );
+        self.gh
+            .api(params.0.account.as_deref(), &endpoint, Some("DELETE"), None)
+            .await
+            .map_err(Self::err)?;
+        Ok(CallToolResult::success(vec![Content::text(format!(
+            "Label '{}' deleted successfully",
+            params.0.name
+        ))]))
+    }
+
+    /// Add labels to an issue or pull request
+    #[tool(description = "Add labels to an issue or pull request. Returns the resulting label set.")]
+    async fn add_labels(&self, params: Parameters<AddLabelsRequest>) -> Result<CallToolResult, McpError> {
+        let endpoint = format!(
+            "repos/{}/{}/issues/{}/labels",
+            params.0.owner, params.0.repo, params.0.number
+        );
+        let body = serde_json::json!({ "labels": params.0.labels });
+        let result = self
+            .gh
+            .api_json(params.0.account.as_deref(), &endpoint, "POST", &body)
+            .await
+            .map_err(Self::err)?;
+        self.respond_compact(result, &params.0.output, &["name"])
+    }
+
+    /// Remove a label from an issue or pull request
+    #[tool(description = "Remove a label from an issue or pull request.")]
+    async fn remove_label(&self, params: Parameters<RemoveLabelRequest>) -> Result<CallToolResult, McpError> {
+        let endpoint = format!(
+            "repos/{}/{}/issues/{}/labels/{}",
+            params.0.owner,
+            params.0.repo,
+            params.0.number,
+            encode_segment(&params.0.label)
+        );
+        self.gh
+            .api(params.0.account.as_deref(), &endpoint, Some("DELETE"), None)
+            .await
+            .map_err(Self::err)?;
+        Ok(CallToolResult::success(vec![Content::text(format!(
+            "Label '{}' removed from #{}",
+            params.0.label, params.0.number
+        ))]))
+    }
+
+    /// Make a repository's labels match a spec
+    #[tool(
+        description = "Make a repository's labels match a spec: creates missing labels, updates color/description/name casing, and with delete_missing deletes labels not in the spec. Returns the changes; use dry_run to preview them."
+    )]
+    async fn sync_labels(&self, params: Parameters<SyncLabelsRequest>) -> Result<CallToolResult, McpError> {
+        let account = params.0.account.as_deref();
+        let endpoint = format!("repos/{}/{}/labels", params.0.owner, params.0.repo);
+        let existing = self.gh.api_list(account, &endpoint).await.map_err(Self::err)?;
+        let plan = plan_label_sync(&existing, &params.0.labels, params.0.delete_missing.unwrap_or(false));
+
+        let dry_run = params.0.dry_run.unwrap_or(false);
+        let mut errors = Vec::new();
+        if !dry_run {
+            for label in &plan.create {
+                let mut body = label_body(label);
+                body["name"] = Value::String(label.name.clone());
+                if let Err(e) = self.gh.api_json(account, &endpoint, "POST", &body).await {
+                    errors.push(format!("create '{}': {e}", label.name));
+                }
+            }
+            for update in &plan.update {
+                let mut body = label_body(&update.to);
+                body["new_name"] = Value::String(update.to.name.clone());
+                let label_endpoint = format!("{endpoint}/{}", encode_segment(&update.name));
+                if let Err(e) = self.gh.api_json(account, &label_endpoint, "PATCH", &body).await {
+                    errors.push(format!("update '{}': {e}", update.name));
+                }
+            }
+            for name in &plan.delete {
+                let label_endpoint = format!("{endpoint}/{}", encode_segment(name));
+                if let Err(e) = self.gh.api(account, &label_endpoint, Some("DELETE"), None).await {
+                    errors.push(format!("delete '{name}': {e}"));
+                }
+            }
+        }
+
+        let result = serde_json::json!({
+            "dry_run": dry_run,
+            "in_sync": plan.is_empty(),
+            "create": plan.create,
+            "update": plan.update,
+            "delete": plan.delete,
+            "unchanged": plan.unchanged.len(),
+            "errors": errors,
+        });
+        self.respond(result, &params.0.output)
+    }
+
+    /// List milestones in a repository
+    #[tool(description = "List milestones in a repository.")]
+    async fn list_milestones(&self, params: Parameters<ListMilestonesRequest>) -> Result<CallToolResult, McpError> {
+        let state = params.0.state.as_deref().unwrap_or("open");
+        let endpoint = format!(
+            "repos/{}/{}/milestones?state={state}&per_page=100",
+            params.0.owner, params.0.repo
+        );
+        let result = self
+            .gh
+            .api(params.0.account.as_deref(), &endpoint, None, None)
+            .await
+            .map_err(Self::err)?;
+        self.respond_compact(
+            result,
+            &params.0.output,
+            &["number", "title", "state", "due_on", "open_issues", "closed_issues"],
+        )
+    }
+
+    /// Create a milestone
+    #[tool(description = "Create a milestone in a repository.")]
+    async fn create_milestone(&self, params: Parameters<CreateMilestoneRequest>) -> Result<CallToolResult, McpError> {
+        let endpoint = format!("repos/{}/{}/milestones", params.0.owner, params.0.repo);
+        let mut body = serde_json::json!({ "title": params.0.title });
+        if let Some(ref description) = params.0.description {
+            body["description"] = Value::String(description.clone());
+        }
+        if let Some(ref due_on) = params.0.due_on {
+            body["due_on"] = Value::String(due_on.clone());
+        }
+
+        let result = self
+            .gh
+            .api_json(params.0.account.as_deref(), &endpoint, "POST", &body)
+            .await
+            .map_err(Self::err)?;
+        self.respond(result, &params.0.output)
+    }
+
+    /// Update a milestone
+    #[tool(description = "Update a milestone's title, description, due date or state (open/closed).")]
+    async fn update_milestone(&self, params: Parameters<UpdateMilestoneRequest>) -> Result<CallToolResult, McpError> {
+        let endpoint = format!(
+            "repos/{}/{}/milestones/{}",
+            params.0.owner, params.0.repo, params.0.number
+        );
+        let mut body = serde_json::json!({});
+        for (key, value) in [
+            ("title", &params.0.title),
+            ("description", &params.0.description),
+            ("due_on", &params.0.due_on),
+            ("state", &params.0.state),
+        ] {
+            if let Some(value) = value {
+                body[key] = Value::String(value.clone());
+            }
+        }
+
+        let result = self
+            .gh
+            .api_json(params.0.account.as_deref(), &endpoint, "PATCH", &body)
+            .await
+            .map_err(Self::err)?;
+        self.respond(result, &params.0.output)
+    }
+
+    // ============================================
+    // Notification Tools
+    // ============================================
+
+    /// List notifications
+    #[tool(
+        description = "List notifications (unread by default) for one account, or for every account with all_accounts. Filter by repo, reason, participating or since."
+    )]
+    async fn list_notifications(&self, params: Parameters<ListNotificationsRequest>) -> Result<CallToolResult, McpError> {
+        let endpoint = params.0.endpoint();
+        let reason = params.0.reason.clone();
+        let limit = params.0.limit.unwrap_or(50) as usize;
+
+        if params.0.all_accounts.unwrap_or(false) {
+            let per_account = self
+                .gh
+                .for_each_account(move |gh, account| {
+                    let endpoint = endpoint.clone();
+                    let reason = reason.clone();
+                    async move {
+                        let account = Some(account.as_str());
+                        collect_notifications(&gh, account, &endpoint, reason.as_deref(), limit, account).await
+                    }
+                })
+                .await;
+            return self.respond(merge_notifications(per_account), &params.0.output);
+        }
+
+        let account = params.0.account.as_deref();
+        let notifications = collect_notifications(&self.gh, account, &endpoint, reason.as_deref(), limit, None)
+            .await
+            .map_err(Self::err)?;
+        self.respond(Value::Array(notifications), &params.0.output)
+    }
+
+    /// Mark a notification thread as read
+    #[tool(description = "Mark a notification thread as read.")]
+    async fn mark_notification_read(
+        &self,
+        params: Parameters<MarkNotificationReadRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let endpoint = format!("notifications/threads/{}", params.0.thread_id);
+        self.gh
+            .api(params.0.account.as_deref(), &endpoint, Some("PATCH"), None)
+            .await
+            .map_err(Self::err)?;
+        Ok(CallToolResult::success(vec![Content::text(format!(
+            "Notification thread {} marked as read",
+            params.0.thread_id
+        ))]))
+    }
+
+    /// Mark all notifications as read
+    #[tool(description = "Mark all notifications as read, for one repository or for all repositories.")]
+    async fn mark_all_read(&self, params: Parameters<MarkAllReadRequest>) -> Result<CallToolResult, McpError> {
+        let endpoint = match params.0.repo {
+            Some(ref repo) => format!("repos/{repo}/notifications"),
+            None => "notifications".to_string(),
+        };
+        let mut body = serde_json::json!({ "read": true });
+        if let Some(ref last_read_at) = params.0.last_read_at {
+            body["last_read_at"] = Value::String(last_read_at.clone());
+        }
+
+        self.gh
+            .api_json(params.0.account.as_deref(), &endpoint, "PUT", &body)
+            .await
+            .map_err(Self::err)?;
+        Ok(CallToolResult::success(vec![Content::text(match params.0.repo {
+            Some(ref repo) => format!("Notifications for {repo} marked as read"),
+            None => "All notifications marked as read".to_string(),
+        })]))
+    }
+
+    /// Subscribe to a notification thread
+    #[tool(description = "Subscribe to a notification thread so you are notified of all activity on it.")]
+    async fn subscribe_thread(&self, params: Parameters<ThreadSubscriptionRequest>) -> Result<CallToolResult, McpError> {
+        let endpoint = format!("notifications/threads/{}/subscription", params.0.thread_id);
+        let body = serde_json::json!({ "ignored": false });
+        let result = self
+            .gh
+            .api_json(params.0.account.as_deref(), &endpoint, "PUT", &body)
+            .await
+            .map_err(Self::err)?;
+        self.respond(result, &params.0.output)
+    }
+
+    /// Unsubscribe from a notification thread
+    #[tool(
+        description = "Unsubscribe from a notification thread. You won't be notified again until you comment or are @mentioned."
+    )]
+    async fn unsubscribe_thread(
+        &self,
+        params: Parameters<ThreadSubscriptionRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let endpoint = format!("notifications/threads/{}/subscription", params.0.thread_id);
+        self.gh
+            .api(params.0.account.as_deref(), &endpoint, Some("DELETE"), None)
+            .await
+            .map_err(Self::err)?;
+        Ok(CallToolResult::success(vec![Content::text(format!(
+            "Unsubscribed from notification thread {}",
+            params.0.thread_id
+        ))]))
+    }
+
+    // ============================================
+    // Actions Secrets and Variables Tools
+    // ============================================
+
+    /// List Actions secrets
+    #[tool(
+        description = "List Actions secret names for a repository, a repository environment, or an organization (omit repo). Values are never returned."
+    )]
+    async fn list_secrets(&self, params: Parameters<ListActionsItemsRequest>) -> Result<CallToolResult, McpError> {
+        let endpoint = params.0.scope.collection("secrets").map_err(Self::err)?;
+        let result = self
+            .gh
+            .api_list_wrapped(params.0.account.as_deref(), &endpoint, "secrets", 100)
+            .await
+            .map_err(Self::err)?;
+        self.respond_compact(
+            result,
+            &params.0.output,
+            &["total_count", "secrets.name", "secrets.updated_at", "secrets.visibility"],
+        )
+    }
+
+    /// Create or update an Actions secret
+    #[tool(
+        description = "Create or update an Actions secret on a repository, a repository environment, or an organization (omit repo). The value is encrypted locally with the scope's public key and is never logged or returned."
+    )]
+    async fn set_secret(&self, params: Parameters<SetSecretRequest>) -> Result<CallToolResult, McpError> {
+        let account = params.0.account.as_deref();
+        let endpoint = params.0.scope.collection("secrets").map_err(Self::err)?;
+
+        let key = self
+            .gh
+            .api(account, &format!("{endpoint}/public-key"), None, None)
+            .await
+            .map_err(Self::err)?;
+        let (Some(key_id), Some(public_key)) = (key["key_id"].as_str(), key["key"].as_str()) else {
+            return Err(Self::err(format!("no public key returned for {endpoint}")));
+        };
+        let encrypted = secret::seal(public_key, &params.0.value).map_err(Self::err)?;
+
+        let mut body = serde_json::json!({ "encrypted_value": encrypted, "key_id": key_id });
+        Self::add_org_visibility(
+            &mut body,
+            &params.0.scope,
+            &params.0.visibility,
+            &params.0.selected_repository_ids,
+        );
+
+        self.gh
+            .api_json(account, &format!("{endpoint}/{}", params.0.name), "PUT", &body)
+            .await
+            .map_err(Self::err)?;
+        Ok(CallToolResult::success(vec![Content::text(format!(
+            "Secret '{}' set on {endpoint}",
+            params.0.name
+        ))]))
+    }
+
+    /// Delete an Actions secret
+    #[tool(description = "Delete an Actions secret from a repository, a repository environment, or an organization.")]
+    async fn delete_secret(&self, params: Parameters<DeleteActionsItemRequest>) -> Result<CallToolResult, McpError> {
+        let endpoint = params.0.scope.collection("secrets").map_err(Self::err)?;
+        self.gh
+            .api(
+                params.0.account.as_deref(),
+                &format!("{endpoint}/{}", params.0.name),
+                Some("DELETE"),
+                None,
+            )
+            .await
+            .map_err(Self::err)?;
+        Ok(CallToolResult::success(vec![Content::text(format!(
+            "Secret '{}' deleted from {endpoint}",
+            params.0.name
+        ))]))
+    }
+
+    /// List Actions variables
+    #[tool(
+        description = "List Actions variables (names and values) for a repository, a repository environment, or an organization (omit repo)."
+    )]
+    async fn list_variables(&self, params: Parameters<ListActionsItemsRequest>) -> Result<CallToolResult, McpError> {
+        let endpoint = params.0.scope.collection("variables").map_err(Self::err)?;
+        let result = self
+            .gh
+            .api_list_wrapped(params.0.account.as_deref(), &endpoint, "variables", 30)
+            .await
+            .map_err(Self::err)?;
+        self.respond_compact(
+            result,
+            &params.0.output,
+            &["total_count", "variables.name", "variables.value", "variables.updated_at"],
+        )
+    }
+
+    /// Create or update an Actions variable
+    #[tool(
+        description = "Create or update an Actions variable on a repository, a repository environment, or an organization (omit repo)."
+    )]
+    async fn set_variable(&self, params: Parameters<SetVariableRequest>) -> Result<CallToolResult, McpError> {
+        let account = params.0.account.as_deref();
+        let endpoint = params.0.scope.collection("variables").map_err(Self::err)?;
+
+        let mut body = serde_json::json!({ "name": params.0.name, "value": params.0.value });
+        Self::add_org_visibility(
+            &mut body,
+            &params.0.scope,
+            &params.0.visibility,
+            &params.0.selected_repository_ids,
+        );
+
+        // Variables have separate create and update calls; try updating first
+        let updated = self
+            .gh
+            .api_json(account, &format!("{endpoint}/{}", params.0.name), "PATCH", &body)
+            .await;
+        let action = match updated {
+            Ok(_) => "updated",
+            Err(e) if e.is_not_found() => {
+                self.gh
+                    .api_json(account, &endpoint, "POST", &body)
+                    .await
+                    .map_err(Self::err)?;
+                "created"
+            }
+            Err(e) => return Err(Self::err(e)),
+        };
+        Ok(CallToolResult::success(vec![Content::text(format!(
+            "Variable '{}' {action} on {endpoint}",
+            params.0.name
+        ))]))
+    }
+
+    /// Delete an Actions variable
+    #[tool(description = "Delete an Actions variable from a repository, a repository environment, or an organization.")]
+    async fn delete_variable(&self, params: Parameters<DeleteActionsItemRequest>) -> Result<CallToolResult, McpError> {
+        let endpoint = params.0.scope.collection("variables").map_err(Self::err)?;
+        self.gh
+            .api(
+                params.0.account.as_deref(),
+                &format!("{endpoint}/{}", params.0.name),
+                Some("DELETE"),
+                None,
+            )
+            .await
+            .map_err(Self::err)?;
+        Ok(CallToolResult::success(vec![Content::text(format!(
+            "Variable '{}' deleted from {endpoint}",
+            params.0.name
+        ))]))
+    }
+
+    // ============================================
+    // Webhook Tools
+    // ============================================
+
+    /// List webhooks
+    #[tool(
+        description = "List webhooks on a repository or an organization (omit repo), with their events, URL and last response. Secrets are redacted."
+    )]
+    async fn list_webhooks(&self, params: Parameters<ListWebhooksRequest>) -> Result<CallToolResult, McpError> {
+        let endpoint = format!("{}?per_page=100", params.0.scope.collection());
+        let mut result = self
+            .gh
+            .api(params.0.account.as_deref(), &endpoint, None, None)
+            .await
+            .map_err(Self::err)?;
+        secret::redact_secrets(&mut result);
+        self.respond_compact(
+            result,
+            &params.0.output,
+            &["id", "active", "events", "config.url", "config.content_type", "last_response", "updated_at"],
+        )
+    }
+
+    /// Get a webhook
+    #[tool(description = "Get a webhook's configuration by ID. Secrets are redacted.")]
+    async fn get_webhook(&self, params: Parameters<WebhookRequest>) -> Result<CallToolResult, McpError> {
+        let endpoint = format!("{}/{}", params.0.scope.collection(), params.0.hook_id);
+        let mut result = self
+            .gh
+            .api(params.0.account.as_deref(), &endpoint, None, None)
+            .await
+            .map_err(Self::err)?;
+        secret::redact_secrets(&mut result);
+        self.respond(result, &params.0.output)
+    }
+
+    /// Create a webhook
+    #[tool(
+        description = "Create a webhook on a repository or an organization (omit repo). The signing secret is never logged or returned."
+    )]
+    async fn create_webhook(&self, params: Parameters<CreateWebhookRequest>) -> Result<CallToolResult, McpError> {
+        if params.0.config.url.is_none() {
+            return Err(Self::err("url is required to create a webhook"));
+        }
+        let mut config = params.0.config.to_body();
+        if config.get("content_type").is_none() {
+            config["content_type"] = serde_json::json!("json");
+        }
+        let body = serde_json::json!({
+            "name": "web",
+            "config": config,
+            "events": params.0.events.clone().unwrap_or_else(|| vec!["push".to_string()]),
+            "active": params.0.active.unwrap_or(true),
+        });
+
+        let mut result = self
+            .gh
+            .api_json(params.0.account.as_deref(), &params.0.scope.collection(), "POST", &body)
+            .await
+            .map_err(Self::err)?;
+        secret::redact_secrets(&mut result);
+        self.respond_compact(
+            result,
+            &params.0.output,
+            &["id", "active", "events", "config.url", "config.content_type", "created_at"],
+        )
+    }
+
+    /// Update a webhook
+    #[tool(
+        description = "Update a webhook's URL, content type, secret, TLS verification, events or active state. Unset fields keep their current value; the secret is never logged or returned."
+    )]
+    async fn update_webhook(&self, params: Parameters<UpdateWebhookRequest>) -> Result<CallToolResult, McpError> {
+        let account = params.0.account.as_deref();
+        let endpoint = format!("{}/{}", params.0.scope.collection(), params.0.hook_id);
+        if params.0.config.is_empty() && params.0.events.is_none() && params.0.active.is_none() {
+            return Err(Self::err("nothing to update"));
+        }
+
+        // Config fields go through the config endpoint, which leaves the ones not sent unchanged
+        if !params.0.config.is_empty() {
+            self.gh
+                .api_json(account, &format!("{endpoint}/config"), "PATCH", &params.0.config.to_body())
+                .await
+                .map_err(Self::err)?;
+        }
+        let mut body = serde_json::json!({});
+        if let Some(ref events) = params.0.events {
+            body["events"] = serde_json::json!(events);
+        }
+        if let Some(active) = params.0.active {
+            body["active"] = serde_json::json!(active);
+        }
+        let mut result = if body.as_object().is_some_and(|b| !b.is_empty()) {
+            self.gh.api_json(account, &endpoint, "PATCH", &body).await
+        } else {
+            self.gh.api(account, &endpoint, None, None).await
+        }
+        .map_err(Self::err)?;
+        secret::redact_secrets(&mut result);
+        self.respond_compact(
+            result,
+            &params.0.output,
+            &["id", "active", "events", "config.url", "config.content_type", "updated_at"],
+        )
+    }
+
+    /// Delete a webhook
+    #[tool(description = "Delete a webhook from a repository or an organization.")]
+    async fn delete_webhook(&self, params: Parameters<WebhookRequest>) -> Result<CallToolResult, McpError> {
+        let endpoint = format!("{}/{}", params.0.scope.collection(), params.0.hook_id);
+        self.gh
+            .api(params.0.account.as_deref(), &endpoint, Some("DELETE"), None)
+            .await
+            .map_err(Self::err)?;
+        Ok(CallToolResult::success(vec![Content::text(format!(
+            "Webhook {} deleted from {}",
+            params.0.hook_id, params.0.scope.owner
+        ))]))
+    }
+
+    /// Ping a webhook
+    #[tool(description = "Send a ping event to a webhook. Check the result with list_webhook_deliveries.")]
+    async fn ping_webhook(&self, params: Parameters<WebhookRequest>) -> Result<CallToolResult, McpError> {
+        let endpoint = format!("{}/{}/pings", params.0.scope.collection(), params.0.hook_id);
+        self.gh
+            .api(params.0.account.as_deref(), &endpoint, Some("POST"), None)
+            .await
+            .map_err(Self::err)?;
+        Ok(CallToolResult::success(vec![Content::text(format!(
+            "Ping sent to webhook {}",
+            params.0.hook_id
+        ))]))
+    }
+
+    /// List recent webhook deliveries
+    #[tool(
+        description = "List a webhook's recent deliveries with event, status code and duration, newest first. Use failed_only to find failing deliveries."
+    )]
+    async fn list_webhook_deliveries(
+        &self,
+        params: Parameters<ListWebhookDeliveriesRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let endpoint = format!(
+            "{}/{}/deliveries?per_page={}",
+            params.0.scope.collection(),
+            params.0.hook_id,
+            params.0.limit.unwrap_or(30).min(100)
+        );
+        let mut result = self
+            .gh
+            .api(params.0.account.as_deref(), &endpoint, None, None)
+            .await
+            .map_err(Self::err)?;
+        if params.0.failed_only.unwrap_or(false)
+            && let Some(deliveries) = result.as_array_mut()
+        {
+            deliveries.retain(delivery_failed);
+        }
+        self.respond_compact(
+            result,
+            &params.0.output,
+            &["id", "guid", "delivered_at", "redelivery", "duration", "status", "status_code", "event", "action"],
+        )
+    }
+
+    /// Get a webhook delivery
+    #[tool(
+        description = "Get a webhook delivery's full request (headers and payload) and response (headers and body), for debugging a failed delivery. Secrets are redacted."
+    )]
+    async fn get_webhook_delivery(&self, params: Parameters<WebhookDeliveryRequest>) -> Result<CallToolResult, McpError> {
+        let endpoint = format!(
+            "{}/{}/deliveries/{}",
+            params.0.scope.collection(),
+            params.0.hook_id,
+            params.0.delivery_id
+        );
+        let mut result = self
+            .gh
+            .api(params.0.account.as_deref(), &endpoint, None, None)
+            .await
+            .map_err(Self::err)?;
+        secret::redact_secrets(&mut result);
+        self.respond(result, &params.0.output)
+    }
+
+    /// Redeliver a webhook delivery
+    #[tool(description = "Redeliver a webhook delivery, e.g. after fixing the receiving service.")]
+    async fn redeliver_webhook(&self, params: Parameters<WebhookDeliveryRequest>) -> Result<CallToolResult, McpError> {
+        let endpoint = format!(
+            "{}/{}/deliveries/{}/attempts",
+            params.0.scope.collection(),
+            params.0.hook_id,
+            params.0.delivery_id
+        );
+        self.gh
+            .api(params.0.account.as_deref(), &endpoint, Some("POST"), None)
+            .await
+            .map_err(Self::err)?;
+        Ok(CallToolResult::success(vec![Content::text(format!(
+            "Redelivery of delivery {} requested for webhook {}",
+            params.0.delivery_id, params.0.hook_id
+        ))]))
+    }
+
+    // ============================================
+    // Batch Tools
+    // ============================================
+
+    /// Run several tool calls in one request
+    #[tool(
+        description = "Run up to 50 calls of other tools in one request, e.g. get_repo for ten repositories. Calls run concurrently, or in order stopping at the first failure with stop_on_error. Results come back in invocation order, each with its index and status ok, error or skipped (not run after a stop_on_error failure). Large results are cut to a share of the response budget."
+    )]
+    async fn batch(
+        &self,
+        params: Parameters<BatchRequest>,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let BatchRequest {
+            account,
+            invocations,
+            stop_on_error,
+            output,
+        } = params.0;
+        if invocations.len() > MAX_BATCH_SIZE {
+            return Err(Self::err(format!("a batch can hold at most {MAX_BATCH_SIZE} invocations")));
+        }
+        if invocations.iter().any(|invocation| invocation.tool == "batch") {
+            return Err(Self::err("batch calls can't be nested"));
+        }
+
+        let calls: Vec<CallToolRequestParam> = invocations
+            .into_iter()
+            .map(|mut invocation| {
+                if let Some(ref account) = account {
+                    invocation
+                        .arguments
+                        .entry("account")
+                        .or_insert_with(|| Value::String(account.clone()));
+                }
+                CallToolRequestParam {
+                    name: invocation.tool.into(),
+                    arguments: Some(invocation.arguments),
+                }
+            })
+            .collect();
+        let tools: Vec<String> = calls.iter().map(|call| call.name.to_string()).collect();
+
+        let mut outcomes: Vec<Option<Result<CallToolResult, McpError>>> = Vec::new();
+        if stop_on_error.unwrap_or(false) {
+            for call in calls {
+                let outcome = self.tool_router.call(ToolCallContext::new(self, call, context.clone())).await;
+                let failed = outcome.as_ref().map_or(true, |result| result.is_error == Some(true));
+                outcomes.push(Some(outcome));
+                if failed {
+                    break;
+                }
+            }
+            outcomes.resize_with(tools.len(), || None);
+        } else {
+            let mut tasks = JoinSet::new();
+            let mut indices = HashMap::new();
+            for (index, call) in calls.into_iter().enumerate() {
+                let server = self.clone();
+                let context = context.clone();
+                let handle = tasks.spawn(async move {
+                    let outcome = server.tool_router.call(ToolCallContext::new(&server, call, context)).await;
+                    (index, outcome)
+                });
+                indices.insert(handle.id(), index);
+            }
+            outcomes.resize_with(tools.len(), || None);
+            while let Some(joined) = tasks.join_next().await {
+                match joined {
+                    Ok((index, outcome)) => outcomes[index] = Some(outcome),
+                    // A panicked or cancelled call still reports as an error at its index
+                    Err(e) => {
+                        warn!("Batch task failed: {}", e);
+                        if let Some(&index) = indices.get(&e.id()) {
+                            outcomes[index] = Some(Err(McpError::internal_error(format!("tool call failed: {e}"), None)));
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut results = Vec::new();
+        let mut failed = 0;
+        for (index, (tool, outcome)) in tools.into_iter().zip(outcomes).enumerate() {
+            let entry = match outcome {
+                Some(Ok(result)) if result.is_error != Some(true) => serde_json::json!({
+                    "index": index, "tool": tool, "status": "ok", "result": batch::result_value(&result)
+                }),
+                Some(Ok(result)) => {
+                    failed += 1;
+                    serde_json::json!({
+                        "index": index, "tool": tool, "status": "error", "error": batch::result_value(&result)
+                    })
+                }
+                Some(Err(e)) => {
+                    failed += 1;
+                    serde_json::json!({ "index": index, "tool": tool, "status": "error", "error": e.message })
+                }
+                None => serde_json::json!({ "index": index, "tool": tool, "status": "skipped" }),
+            };
+            results.push(entry);
+        }
+        batch::fit_results(&mut results, self.gh.config().output.max_response_bytes);
+
+        let result = serde_json::json!({ "failed": failed, "results": results });
+        self.respond(result, &output)
     }
 }
 
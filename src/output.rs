@@ -0,0 +1,462 @@
+//! Output shaping for tool results: field selection, jq filters and response size budgets
+
+use crate::diff;
+use crate::{Error, Result};
+use rmcp::schemars;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+/// Output options accepted by every tool that returns JSON
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+pub struct OutputOptions {
+    /// JSON paths to keep
+    #[schemars(
+        description = "Dot-separated JSON paths to keep (e.g. ['name', 'commit.sha']); arrays are projected element-wise. Use ['*'] for the full output. List tools default to a compact projection."
+    )]
+    pub fields: Option<Vec<String>>,
+
+    /// jq filter applied to the result
+    #[schemars(description = "jq filter applied to the result after field selection (e.g. '.[] | select(.protected)')")]
+    pub jq: Option<String>,
+}
+
+impl OutputOptions {
+    /// Apply field selection and the jq filter to a result.
+    ///
+    /// `default_fields` is the tool's compact projection, used when the caller gives neither
+    /// `fields` nor `jq`.
+    pub fn apply(&self, value: Value, default_fields: &[&str]) -> Result<Value> {
+        let value = match (&self.fields, &self.jq) {
+            (Some(fields), _) if fields.iter().any(|f| f == "*") => value,
+            (Some(fields), _) => project(&value, fields),
+            (None, None) if !default_fields.is_empty() => project(&value, default_fields),
+            _ => value,
+        };
+
+        match self.jq {
+            Some(ref filter) => apply_jq(value, filter),
+            None => Ok(value),
+        }
+    }
+}
+
+/// Keep only the given dot-separated paths, projecting arrays element-wise
+pub fn project<S: AsRef<str>>(value: &Value, paths: &[S]) -> Value {
+    let paths: Vec<Vec<&str>> = paths.iter().map(|p| p.as_ref().split('.').collect()).collect();
+    let paths: Vec<&[&str]> = paths.iter().map(Vec::as_slice).collect();
+    project_paths(value, &paths)
+}
+
+fn project_paths(value: &Value, paths: &[&[&str]]) -> Value {
+    match value {
+        Value::Array(items) => Value::Array(items.iter().map(|item| project_paths(item, paths)).collect()),
+        Value::Object(object) => {
+            let mut projected = Map::new();
+            for (i, path) in paths.iter().enumerate() {
+                let Some((key, _)) = path.split_first() else {
+                    continue;
+                };
+                // Handle each top-level key once, together with all paths below it
+                if projected.contains_key(*key) || paths[..i].iter().any(|p| p.first() == Some(key)) {
+                    continue;
+                }
+                let Some(child) = object.get(*key) else {
+                    continue;
+                };
+
+                let rests: Vec<&[&str]> = paths
+                    .iter()
+                    .filter(|p| p.first() == Some(key))
+                    .map(|p| &p[1..])
+                    .collect();
+                let child = if rests.iter().any(|r| r.is_empty()) {
+                    child.clone()
+                } else {
+                    project_paths(child, &rests)
+                };
+                projected.insert(key.to_string(), child);
+            }
+            Value::Object(projected)
+        }
+        other => other.clone(),
+    }
+}
+
+/// Run a jq filter over a value. A single output is returned as-is; multiple outputs as an array.
+pub fn apply_jq(value: Value, filter: &str) -> Result<Value> {
+    use jaq_core::compile::Undefined;
+    use jaq_core::load::{Arena, File, Loader};
+    use jaq_core::{Compiler, Ctx, RcIter};
+    use jaq_json::Val;
+
+    let invalid = |e: String| Error::Tool(format!("invalid jq filter '{filter}': {e}"));
+
+    let loader = Loader::new(jaq_std::defs().chain(jaq_json::defs()));
+    let arena = Arena::default();
+    let modules = loader
+        .load(&arena, File { code: filter, path: () })
+        .map_err(|_| invalid("syntax error".to_string()))?;
+    let compiled = Compiler::default()
+        .with_funs(jaq_std::funs().chain(jaq_json::funs()))
+        .compile(modules)
+        .map_err(|errs| {
+            let undefined: Vec<String> = errs
+                .into_iter()
+                .flat_map(|(_, errs)| errs)
+                .map(|(name, kind)| match kind {
+                    Undefined::Filter(arity) => format!("undefined filter '{name}/{arity}'"),
+                    other => format!("undefined {} '{name}'", format!("{other:?}").to_lowercase()),
+                })
+                .collect();
+            invalid(undefined.join(", "))
+        })?;
+
+    let inputs = RcIter::new(core::iter::empty());
+    let mut outputs = Vec::new();
+    for output in compiled.run((Ctx::new([], &inputs), Val::from(value))) {
+        let output = output.map_err(|e| Error::Tool(format!("jq error: {e}")))?;
+        outputs.push(Value::from(output));
+    }
+
+    Ok(match outputs.len() {
+        1 => outputs.remove(0),
+        _ => Value::Array(outputs),
+    })
+}
+
+/// What was left out when fitting a result into the response budget
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Omission {
+    /// Number of units kept
+    pub kept: usize,
+
+    /// Number of units in the full result
+    pub total: usize,
+
+    /// What was counted (e.g. "items", "files", "bytes of 'body'")
+    pub unit: String,
+
+    /// Bytes dropped from the serialized result
+    pub omitted_bytes: usize,
+
+    /// Names of omitted entries, where meaningful (e.g. file paths of a diff)
+    pub omitted: Vec<String>,
+}
+
+impl Omission {
+    /// Footer telling the model what was dropped and how to fetch it
+    pub fn footer(&self, budget: usize, hint: &str) -> String {
+        let mut footer = format!(
+            "[Truncated to the {budget}-byte response budget: showing {} of {} {}, {} bytes omitted.",
+            self.kept, self.total, self.unit, self.omitted_bytes
+        );
+        if !self.omitted.is_empty() {
+            const MAX_NAMES: usize = 50;
+            let names: Vec<&str> = self.omitted.iter().take(MAX_NAMES).map(String::as_str).collect();
+            footer.push_str(&format!(" Omitted: {}", names.join(", ")));
+            if self.omitted.len() > MAX_NAMES {
+                footer.push_str(&format!(" (+{} more)", self.omitted.len() - MAX_NAMES));
+            }
+            footer.push('.');
+        }
+        footer.push_str(&format!(" {hint}]"));
+        footer
+    }
+}
+
+fn json_len(value: &Value) -> usize {
+    serde_json::to_string(value).map(|s| s.len()).unwrap_or_default()
+}
+
+/// Keep whole items from the front of a list while they fit in `budget` bytes
+fn fit_items(items: Vec<Value>, budget: usize) -> (Vec<Value>, usize) {
+    let total = items.len();
+    let mut used = 2; // []
+    let kept: Vec<Value> = items
+        .into_iter()
+        .take_while(|item| {
+            used += json_len(item) + 1;
+            used <= budget
+        })
+        .collect();
+    let kept_len = kept.len();
+    (kept, total - kept_len)
+}
+
+/// Shorten a string to at most `max` bytes, on a char boundary
+fn truncate_str(s: &mut String, max: usize) {
+    if s.len() > max {
+        let mut end = max;
+        while !s.is_char_boundary(end) {
+            end -= 1;
+        }
+        s.truncate(end);
+    }
+}
+
+/// Fit a JSON result into `budget` bytes (0 = unlimited), cutting at item boundaries.
+///
+/// Top-level lists keep whole items from the front. Objects have their largest list field
+/// cut the same way, or failing that their largest string field shortened.
+pub fn fit_json(value: Value, budget: usize) -> (Value, Option<Omission>) {
+    let size = json_len(&value);
+    if budget == 0 || size <= budget {
+        return (value, None);
+    }
+
+    let (value, kept, total, unit) = match value {
+        Value::Array(items) => {
+            let total = items.len();
+            let (kept, _) = fit_items(items, budget);
+            let kept_len = kept.len();
+            (Value::Array(kept), kept_len, total, "items".to_string())
+        }
+        Value::Object(mut object) => {
+            let largest = |object: &Map<String, Value>, pred: fn(&Value) -> bool| {
+                object
+                    .iter()
+                    .filter(|(_, v)| pred(v))
+                    .max_by_key(|(_, v)| json_len(v))
+                    .map(|(k, _)| k.clone())
+            };
+
+            if let Some(key) = largest(&object, Value::is_array) {
+                let items = match object[&key].take() {
+                    Value::Array(items) => items,
+                    _ => unreachable!("selected field is an array"),
+                };
+                let total = items.len();
+                let rest = size.saturating_sub(json_len(&Value::Array(items.clone())));
+                let (kept, _) = fit_items(items, budget.saturating_sub(rest));
+                let kept_len = kept.len();
+                object.insert(key.clone(), Value::Array(kept));
+                (Value::Object(object), kept_len, total, format!("items of '{key}'"))
+            } else if let Some(key) = largest(&object, Value::is_string) {
+                let mut text = object[&key].as_str().unwrap_or_default().to_string();
+                let total = text.len();
+                let rest = size.saturating_sub(json_len(&object[&key]));
+                truncate_str(&mut text, budget.saturating_sub(rest));
+                let kept = text.len();
+                object.insert(key.clone(), Value::String(text));
+                (Value::Object(object), kept, total, format!("bytes of '{key}'"))
+            } else {
+                return (Value::Object(object), None);
+            }
+        }
+        Value::String(mut text) => {
+            let total = text.len();
+            truncate_str(&mut text, budget);
+            let kept = text.len();
+            (Value::String(text), kept, total, "bytes".to_string())
+        }
+        other => return (other, None),
+    };
+
+    let omission = Omission {
+        kept,
+        total,
+        unit,
+        omitted_bytes: size.saturating_sub(json_len(&value)),
+        omitted: Vec::new(),
+    };
+    (value, Some(omission))
+}
+
+/// Fit a unified diff into `budget` bytes (0 = unlimited), keeping whole files.
+///
+/// If not even the first file fits, it is cut at a line boundary.
+pub fn fit_diff(text: &str, budget: usize) -> (String, Option<Omission>) {
+    if budget == 0 || text.len() <= budget {
+        return (text.to_string(), None);
+    }
+
+    let files = diff::split_files(text);
+    let mut kept = String::new();
+    let mut omitted = Vec::new();
+    for file in &files {
+        if omitted.is_empty() && kept.len() + file.text.len() <= budget {
+            kept.push_str(file.text);
+        } else {
+            omitted.push(file.path.to_string());
+        }
+    }
+
+    if kept.is_empty() {
+        for line in text.split_inclusive('\n') {
+            if kept.len() + line.len() > budget {
+                break;
+            }
+            kept.push_str(line);
+        }
+    }
+
+    let omission = Omission {
+        kept: files.len() - omitted.len(),
+        total: files.len(),
+        unit: "files".to_string(),
+        omitted_bytes: text.len() - kept.len(),
+        omitted,
+    };
+    (kept, Some(omission))
+}
+
+/// Fit plain text into `budget` bytes (0 = unlimited), keeping whole lines from the start.
+///
+/// If not even the first line fits, it is cut on a char boundary.
+pub fn fit_text(text: &str, budget: usize) -> (String, Option<Omission>) {
+    if budget == 0 || text.len() <= budget {
+        return (text.to_string(), None);
+    }
+
+    let mut kept = String::new();
+    let mut kept_lines = 0;
+    for line in text.split_inclusive('\n') {
+        if kept.len() + line.len() > budget {
+            break;
+        }
+        kept.push_str(line);
+        kept_lines += 1;
+    }
+    if kept.is_empty() {
+        kept = text.to_string();
+        truncate_str(&mut kept, budget);
+    }
+
+    let omission = Omission {
+        kept: kept_lines,
+        total: text.split_inclusive('\n').count(),
+        unit: "lines".to_string(),
+        omitted_bytes: text.len() - kept.len(),
+        omitted: Vec::new(),
+    };
+    (kept, Some(omission))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn branches() -> Value {
+        json!([
+            {"name": "main", "commit": {"sha": "abc", "url": "https://api.github.com/x"}, "protected": true},
+            {"name": "dev", "commit": {"sha": "def", "url": "https://api.github.com/y"}, "protected": false}
+        ])
+    }
+
+    #[test]
+    fn test_project_nested_paths() {
+        let projected = project(&branches(), &["name", "commit.sha"]);
+        assert_eq!(
+            projected,
+            json!([
+                {"name": "main", "commit": {"sha": "abc"}},
+                {"name": "dev", "commit": {"sha": "def"}}
+            ])
+        );
+    }
+
+    #[test]
+    fn test_project_arrays_inside_objects() {
+        let value = json!({"total_count": 1, "artifacts": [{"id": 1, "name": "build", "archive_download_url": "x"}]});
+        let projected = project(&value, &["total_count", "artifacts.name"]);
+        assert_eq!(projected, json!({"total_count": 1, "artifacts": [{"name": "build"}]}));
+    }
+
+    #[test]
+    fn test_apply_defaults_and_overrides() {
+        let defaults = ["name", "protected"];
+
+        let options = OutputOptions::default();
+        assert_eq!(options.apply(branches(), &defaults).unwrap()[0], json!({"name": "main", "protected": true}));
+
+        let options = OutputOptions {
+            fields: Some(vec!["*".to_string()]),
+            jq: None,
+        };
+        assert_eq!(options.apply(branches(), &defaults).unwrap(), branches());
+
+        // A jq filter sees the full result unless fields are given explicitly
+        let options = OutputOptions {
+            fields: None,
+            jq: Some("[.[] | select(.protected) | .commit.url]".to_string()),
+        };
+        assert_eq!(options.apply(branches(), &defaults).unwrap(), json!(["https://api.github.com/x"]));
+    }
+
+    #[test]
+    fn test_apply_jq_multiple_outputs() {
+        let names = apply_jq(branches(), ".[].name").unwrap();
+        assert_eq!(names, json!(["main", "dev"]));
+    }
+
+    #[test]
+    fn test_apply_jq_invalid() {
+        assert!(matches!(apply_jq(json!({}), ".[[["), Err(Error::Tool(_))));
+        let err = apply_jq(json!({}), "foo(1)").unwrap_err();
+        assert_eq!(err.to_string(), "Tool error: invalid jq filter 'foo(1)': undefined filter 'foo/1'");
+    }
+
+    #[test]
+    fn test_fit_json_list() {
+        let items: Vec<Value> = (0..100).map(|i| json!({"id": i, "name": format!("item-{i}")})).collect();
+        let (value, omission) = fit_json(Value::Array(items), 200);
+        let omission = omission.unwrap();
+
+        assert!(json_len(&value) <= 200);
+        assert_eq!(omission.total, 100);
+        assert_eq!(omission.kept, value.as_array().unwrap().len());
+        assert_eq!(value[0], json!({"id": 0, "name": "item-0"}));
+    }
+
+    #[test]
+    fn test_fit_json_object_list_field() {
+        let artifacts: Vec<Value> = (0..50).map(|i| json!({"id": i})).collect();
+        let value = json!({"total_count": 50, "artifacts": artifacts});
+        let (value, omission) = fit_json(value, 100);
+
+        assert_eq!(value["total_count"], 50);
+        assert!(json_len(&value) <= 100);
+        assert_eq!(omission.unwrap().unit, "items of 'artifacts'");
+    }
+
+    #[test]
+    fn test_fit_json_within_budget() {
+        let (value, omission) = fit_json(branches(), 10_000);
+        assert_eq!(value, branches());
+        assert!(omission.is_none());
+
+        let (_, omission) = fit_json(branches(), 0);
+        assert!(omission.is_none());
+    }
+
+    #[test]
+    fn test_fit_diff_whole_files() {
+        let diff = "diff --git a/a.rs b/a.rs\n--- a/a.rs\n+++ b/a.rs\n@@ -1 +1 @@\n-a\n+b\n\
+diff --git a/b.rs b/b.rs\n--- a/b.rs\n+++ b/b.rs\n@@ -1 +1 @@\n-c\n+d\n";
+        let first_len = diff.find("diff --git a/b.rs").unwrap();
+
+        let (kept, omission) = fit_diff(diff, first_len + 10);
+        let omission = omission.unwrap();
+        assert_eq!(kept.len(), first_len);
+        assert_eq!(omission.kept, 1);
+        assert_eq!(omission.omitted, vec!["b.rs".to_string()]);
+        assert!(omission.footer(first_len + 10, "hint").contains("Omitted: b.rs."));
+    }
+
+    #[test]
+    fn test_fit_text_whole_lines() {
+        let text = "one\ntwo\nthree\n";
+        let (kept, omission) = fit_text(text, 9);
+        let omission = omission.unwrap();
+        assert_eq!(kept, "one\ntwo\n");
+        assert_eq!((omission.kept, omission.total, omission.omitted_bytes), (2, 3, 6));
+
+        let (kept, omission) = fit_text(text, 2);
+        assert_eq!(kept, "on");
+        assert_eq!(omission.unwrap().kept, 0);
+
+        assert!(fit_text(text, 0).1.is_none());
+    }
+}
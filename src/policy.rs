@@ -0,0 +1,797 @@
+//! Declarative repository policy: diff live repository state against a spec and apply the difference
+
+use crate::gh::encode_segment;
+use crate::tools::labels::{LabelSpec, label_body, plan_label_sync};
+use crate::settings::{BranchProtectionSettings, RepoSettings};
+use crate::tools::rulesets::RulesetSettings;
+use crate::{Error, GhClient, Result};
+use rmcp::schemars;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::task::JoinSet;
+
+/// Repositories a policy applies to
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PolicyTarget {
+    /// Account to use (default account if omitted)
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Repositories as owner/name; the name may be a glob such as 'tatari-tv/*'
+    #[schemars(description = "Repositories as 'owner/name'; the name may be a glob such as 'tatari-tv/svc-*'")]
+    pub repos: Vec<String>,
+}
+
+/// Repository access level
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Permission {
+    #[serde(alias = "read")]
+    Pull,
+    Triage,
+    #[serde(alias = "write")]
+    Push,
+    Maintain,
+    Admin,
+}
+
+impl Permission {
+    /// Parse a permission or role name as reported by the API (`read`, `push`, ...)
+    pub fn from_api(name: &str) -> Option<Self> {
+        serde_json::from_value(json!(name)).ok()
+    }
+}
+
+/// Desired state for a set of repositories
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PolicySpec {
+    /// Repositories the policy applies to
+    #[serde(default)]
+    #[schemars(description = "Repositories the policy applies to, per account")]
+    pub targets: Vec<PolicyTarget>,
+
+    /// Repository settings
+    #[schemars(description = "Repository settings (merge options, features, ...); unset fields are not managed")]
+    pub settings: Option<RepoSettings>,
+
+    /// Branch protection, keyed by branch name
+    #[serde(default)]
+    #[schemars(description = "Branch protection keyed by branch name; unset fields are not managed")]
+    pub branch_protection: BTreeMap<String, BranchProtectionSettings>,
+
+    /// Rulesets, keyed by ruleset name
+    #[serde(default)]
+    #[schemars(description = "Repository rulesets keyed by name; rulesets not listed are left alone")]
+    pub rulesets: BTreeMap<String, RulesetSettings>,
+
+    /// Labels
+    #[schemars(description = "Labels every repository should have")]
+    pub labels: Option<Vec<LabelSpec>>,
+
+    /// Delete labels not in the spec
+    #[serde(default)]
+    #[schemars(description = "Delete labels that are not in the spec (default: false)")]
+    pub prune_labels: bool,
+
+    /// Direct collaborators, login to permission
+    #[serde(default)]
+    #[schemars(description = "Direct collaborators: login to permission (pull, triage, push, maintain, admin)")]
+    pub collaborators: BTreeMap<String, Permission>,
+
+    /// Remove direct collaborators not in the spec
+    #[serde(default)]
+    #[schemars(description = "Remove direct collaborators that are not in the spec (default: false)")]
+    pub prune_collaborators: bool,
+
+    /// Team access, team slug to permission
+    #[serde(default)]
+    #[schemars(description = "Team access for org repositories: team slug to permission")]
+    pub teams: BTreeMap<String, Permission>,
+}
+
+impl PolicySpec {
+    /// Load a spec from a YAML file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let content = fs::read_to_string(&path)
+            .map_err(|e| Error::Config(format!("Failed to read policy {}: {}", path.as_ref().display(), e)))?;
+        Ok(serde_yaml::from_str(&content)?)
+    }
+
+    /// Override the spec's targets: explicit repos replace them, an account fills in targets without one
+    pub fn with_targets(mut self, account: Option<String>, repos: Vec<String>) -> Result<Self> {
+        if !repos.is_empty() {
+            self.targets = vec![PolicyTarget { account, repos }];
+        } else if account.is_some() {
+            for target in self.targets.iter_mut().filter(|t| t.account.is_none()) {
+                target.account = account.clone();
+            }
+        }
+        if self.targets.iter().all(|t| t.repos.is_empty()) {
+            return Err(Error::Config("policy has no target repositories".to_string()));
+        }
+        Ok(self)
+    }
+}
+
+/// What a change does
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    Create,
+    Update,
+    Delete,
+}
+
+impl Action {
+    fn marker(self) -> char {
+        match self {
+            Action::Create => '+',
+            Action::Update => '~',
+            Action::Delete => '-',
+        }
+    }
+}
+
+/// The API calls that carry out a change
+#[derive(Debug, Clone)]
+enum Operation {
+    Api {
+        method: &'static str,
+        endpoint: String,
+        body: Option<Value>,
+    },
+    Protect {
+        branch: String,
+        settings: Box<BranchProtectionSettings>,
+    },
+}
+
+/// One difference between a repository and the spec
+#[derive(Debug, Clone, Serialize)]
+pub struct Change {
+    /// What differs, e.g. `settings`, `branch_protection/main` or `label/bug`
+    pub area: String,
+
+    pub action: Action,
+
+    /// `{field: {from, to}}` for updates, or the desired object for creations
+    #[serde(skip_serializing_if = "Value::is_null")]
+    pub diff: Value,
+
+    /// Error from applying the change
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+
+    #[serde(skip)]
+    operation: Operation,
+}
+
+impl Change {
+    fn api(area: String, action: Action, diff: Value, method: &'static str, endpoint: String, body: Option<Value>) -> Self {
+        Self {
+            area,
+            action,
+            diff,
+            error: None,
+            operation: Operation::Api { method, endpoint, body },
+        }
+    }
+}
+
+/// Differences between one repository and the spec
+#[derive(Debug, Clone, Serialize)]
+pub struct RepoPlan {
+    pub account: String,
+    pub repo: String,
+    pub changes: Vec<Change>,
+
+    /// Parts of the live state that couldn't be read, and so weren't compared
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<String>,
+}
+
+/// Fields of `want` that differ from `have`, as `{field: {from, to}}`.
+///
+/// Only fields set (non-null) in `want` are compared and nested objects are compared field
+/// by field, so unmanaged settings never show up as drift. Arrays compare without regard to order.
+pub fn drift(want: &Value, have: &Value) -> Map<String, Value> {
+    let mut changes = Map::new();
+    let Some(want) = want.as_object() else {
+        return changes;
+    };
+    for (key, w) in want {
+        let h = have.get(key).unwrap_or(&Value::Null);
+        if w.is_null() {
+            continue;
+        }
+        if w.is_object() && h.is_object() {
+            for (nested, change) in drift(w, h) {
+                changes.insert(format!("{key}.{nested}"), change);
+            }
+        } else if !matches(w, h) {
+            changes.insert(key.clone(), json!({ "from": h, "to": w }));
+        }
+    }
+    changes
+}
+
+fn matches(want: &Value, have: &Value) -> bool {
+    match (want, have) {
+        (Value::Object(_), Value::Object(_)) => drift(want, have).is_empty(),
+        (Value::Array(want), Value::Array(have)) => {
+            want.len() == have.len() && want.iter().all(|w| have.iter().any(|h| matches(w, h)))
+        }
+        _ => want == have,
+    }
+}
+
+/// Compare repository settings with the live repository
+fn plan_settings(repo: &str, spec: &RepoSettings, live: &Value) -> Option<Change> {
+    let want = serde_json::to_value(spec).unwrap_or_default();
+    let changes = drift(&want, live);
+    if changes.is_empty() {
+        return None;
+    }
+    let body: Map<String, Value> = changes.keys().map(|key| (key.clone(), want[key].clone())).collect();
+    Some(Change::api(
+        "settings".to_string(),
+        Action::Update,
+        Value::Object(changes),
+        "PATCH",
+        format!("repos/{repo}"),
+        Some(Value::Object(body)),
+    ))
+}
+
+/// Compare a branch's protection with the spec; `live` is None for an unprotected branch.
+///
+/// Settings the spec doesn't mention keep their live value; the diff covers everything the apply
+/// would change, including settings it turns off.
+fn plan_protection(branch: &str, spec: &BranchProtectionSettings, live: Option<&Value>) -> Option<Change> {
+    let current = live.map(BranchProtectionSettings::from_api).unwrap_or_default();
+    let target = current.merge(spec);
+    let changes = current.diff(&target);
+    if changes.is_empty() {
+        return None;
+    }
+    Some(Change {
+        area: format!("branch_protection/{branch}"),
+        action: if live.is_some() { Action::Update } else { Action::Create },
+        diff: Value::Object(changes),
+        error: None,
+        operation: Operation::Protect {
+            branch: branch.to_string(),
+            settings: Box::new(target),
+        },
+    })
+}
+
+/// Compare a ruleset with the spec; `live` is the ruleset's details if one with that name exists
+fn plan_ruleset(repo: &str, name: &str, spec: &RulesetSettings, live: Option<&Value>) -> Result<Option<Change>> {
+    let area = format!("ruleset/{name}");
+    let endpoint = format!("repos/{repo}/rulesets");
+
+    let Some(live) = live else {
+        let mut body = spec.create_body(false)?;
+        body["name"] = json!(name);
+        return Ok(Some(Change::api(area, Action::Create, body.clone(), "POST", endpoint, Some(body))));
+    };
+
+    let mut body = spec.to_body(false)?;
+    body["name"] = json!(name);
+    let changes = drift(&body, live);
+    if changes.is_empty() {
+        return Ok(None);
+    }
+    let endpoint = format!("{endpoint}/{}", live["id"]);
+    Ok(Some(Change::api(area, Action::Update, Value::Object(changes), "PUT", endpoint, Some(body))))
+}
+
+/// Compare labels with the spec
+fn plan_labels(repo: &str, spec: &[LabelSpec], prune: bool, live: &[Value]) -> Vec<Change> {
+    let endpoint = format!("repos/{repo}/labels");
+    let plan = plan_label_sync(live, spec, prune);
+    let mut changes = Vec::new();
+
+    for label in plan.create {
+        let mut body = label_body(&label);
+        body["name"] = json!(label.name);
+        let area = format!("label/{}", label.name);
+        changes.push(Change::api(area, Action::Create, body.clone(), "POST", endpoint.clone(), Some(body)));
+    }
+    for update in plan.update {
+        let current = live
+            .iter()
+            .find(|label| label["name"] == update.name.as_str())
+            .unwrap_or(&Value::Null);
+        let want = json!(update.to);
+        let diff: Map<String, Value> = update
+            .changes
+            .iter()
+            .map(|field| (field.to_string(), json!({ "from": current[field], "to": want[field] })))
+            .collect();
+        let mut body = label_body(&update.to);
+        body["new_name"] = json!(update.to.name);
+        changes.push(Change::api(
+            format!("label/{}", update.name),
+            Action::Update,
+            Value::Object(diff),
+            "PATCH",
+            format!("{endpoint}/{}", encode_segment(&update.name)),
+            Some(body),
+        ));
+    }
+    for name in plan.delete {
+        let label_endpoint = format!("{endpoint}/{}", encode_segment(&name));
+        changes.push(Change::api(format!("label/{name}"), Action::Delete, Value::Null, "DELETE", label_endpoint, None));
+    }
+    changes
+}
+
+/// Compare direct collaborators (and pending invitations) with the spec
+fn plan_collaborators(
+    repo: &str,
+    spec: &BTreeMap<String, Permission>,
+    prune: bool,
+    collaborators: &[Value],
+    invitations: &[Value],
+) -> Vec<Change> {
+    let endpoint = format!("repos/{repo}/collaborators");
+    let owner = repo.split('/').next().unwrap_or_default();
+
+    let mut live: Vec<(&str, &str)> = collaborators
+        .iter()
+        .filter_map(|c| Some((c["login"].as_str()?, c["role_name"].as_str()?)))
+        .collect();
+    live.extend(
+        invitations
+            .iter()
+            .filter_map(|i| Some((i["invitee"]["login"].as_str()?, i["permissions"].as_str()?))),
+    );
+
+    let mut changes = Vec::new();
+    for (login, permission) in spec {
+        let current = live.iter().find(|(l, _)| l.eq_ignore_ascii_case(login)).map(|(_, role)| *role);
+        if current.and_then(Permission::from_api) == Some(*permission) {
+            continue;
+        }
+        let action = if current.is_some() { Action::Update } else { Action::Create };
+        changes.push(Change::api(
+            format!("collaborator/{login}"),
+            action,
+            json!({ "permission": { "from": current, "to": permission } }),
+            "PUT",
+            format!("{endpoint}/{login}"),
+            Some(json!({ "permission": permission })),
+        ));
+    }
+
+    if prune {
+        for collaborator in collaborators {
+            let Some(login) = collaborator["login"].as_str() else {
+                continue;
+            };
+            if login.eq_ignore_ascii_case(owner) || spec.keys().any(|l| l.eq_ignore_ascii_case(login)) {
+                continue;
+            }
+            changes.push(Change::api(
+                format!("collaborator/{login}"),
+                Action::Delete,
+                Value::Null,
+                "DELETE",
+                format!("{endpoint}/{login}"),
+                None,
+            ));
+        }
+    }
+    changes
+}
+
+/// Compare team access with the spec
+fn plan_teams(repo: &str, spec: &BTreeMap<String, Permission>, live: &[Value]) -> Vec<Change> {
+    let owner = repo.split('/').next().unwrap_or_default();
+    spec.iter()
+        .filter_map(|(slug, permission)| {
+            let current = live
+                .iter()
+                .find(|team| team["slug"] == slug.as_str())
+                .and_then(|team| team["permission"].as_str());
+            if current.and_then(Permission::from_api) == Some(*permission) {
+                return None;
+            }
+            let action = if current.is_some() { Action::Update } else { Action::Create };
+            Some(Change::api(
+                format!("team/{slug}"),
+                action,
+                json!({ "permission": { "from": current, "to": permission } }),
+                "PUT",
+                format!("orgs/{owner}/teams/{slug}/repos/{repo}"),
+                Some(json!({ "permission": permission })),
+            ))
+        })
+        .collect()
+}
+
+/// Human-readable summary of plans (or applied plans)
+pub fn render_plan(plans: &[RepoPlan]) -> String {
+    let mut out = String::new();
+    let mut total = 0;
+    let mut drifted = 0;
+
+    for plan in plans {
+        if plan.changes.is_empty() && plan.errors.is_empty() {
+            let _ = writeln!(out, "{} ({}): no changes", plan.repo, plan.account);
+            continue;
+        }
+        let _ = writeln!(out, "{} ({})", plan.repo, plan.account);
+        for change in &plan.changes {
+            let _ = writeln!(out, "  {} {}", change.action.marker(), change.area);
+            if change.action == Action::Update
+                && let Some(fields) = change.diff.as_object()
+            {
+                for (field, diff) in fields {
+                    let _ = writeln!(out, "      {field}: {} -> {}", diff["from"], diff["to"]);
+                }
+            }
+            if let Some(ref error) = change.error {
+                let _ = writeln!(out, "      failed: {error}");
+            }
+        }
+        for error in &plan.errors {
+            let _ = writeln!(out, "  ! {error}");
+        }
+        total += plan.changes.len();
+        drifted += usize::from(!plan.changes.is_empty());
+    }
+
+    let _ = writeln!(out, "\n{total} change(s) across {drifted} of {} repositories", plans.len());
+    out
+}
+
+impl GhClient {
+    /// Resolve a spec's targets to (account, owner/name) pairs, expanding globs against the owner's repositories
+    async fn policy_repos(&self, targets: &[PolicyTarget]) -> Result<Vec<(String, String)>> {
+        let mut repos: Vec<(String, String)> = Vec::new();
+        for target in targets {
+            let account = target.account.clone().unwrap_or_else(|| self.config().default_account.clone());
+            for pattern in &target.repos {
+                let Some((owner, name)) = pattern.split_once('/') else {
+                    return Err(Error::Config(format!("policy target '{pattern}' is not owner/name")));
+                };
+                let matched = if name.contains(['*', '?', '[', '{']) {
+                    self.find_repos(Some(&account), owner, Some(name), None).await?
+                } else {
+                    vec![pattern.clone()]
+                };
+                for repo in matched {
+                    if !repos.iter().any(|(a, r)| *a == account && *r == repo) {
+                        repos.push((account.clone(), repo));
+                    }
+                }
+            }
+        }
+        Ok(repos)
+    }
+
+    /// Diff every target repository against the spec
+    pub async fn plan_policy(&self, spec: PolicySpec) -> Result<Vec<RepoPlan>> {
+        let repos = self.policy_repos(&spec.targets).await?;
+        let spec = Arc::new(spec);
+
+        let mut tasks = JoinSet::new();
+        for (account, repo) in repos {
+            let gh = self.clone();
+            let spec = spec.clone();
+            tasks.spawn(async move { gh.plan_repo(account, repo, &spec).await });
+        }
+
+        let mut plans = Vec::new();
+        while let Some(joined) = tasks.join_next().await {
+            match joined {
+                Ok(plan) => plans.push(plan),
+                Err(e) => tracing::warn!("Policy plan task failed: {}", e),
+            }
+        }
+        plans.sort_by(|a, b| (&a.repo, &a.account).cmp(&(&b.repo, &b.account)));
+        Ok(plans)
+    }
+
+    /// GET every page of a list endpoint, recording a failure in `errors`
+    async fn read_list(&self, account: &str, endpoint: &str, what: &str, errors: &mut Vec<String>) -> Option<Vec<Value>> {
+        match self.api_list(Some(account), endpoint).await {
+            Ok(items) => Some(items),
+            Err(e) => {
+                errors.push(format!("failed to read {what}: {e}"));
+                None
+            }
+        }
+    }
+
+    /// Diff one repository against the spec
+    async fn plan_repo(&self, account: String, repo: String, spec: &PolicySpec) -> RepoPlan {
+        let acct = Some(account.as_str());
+        let mut changes = Vec::new();
+        let mut errors = Vec::new();
+
+        if let Some(ref settings) = spec.settings {
+            match self.api(acct, &format!("repos/{repo}"), None, None).await {
+                Ok(live) => changes.extend(plan_settings(&repo, settings, &live)),
+                Err(e) => errors.push(format!("failed to read settings: {e}")),
+            }
+        }
+
+        for (branch, settings) in &spec.branch_protection {
+            let endpoint = format!("repos/{repo}/branches/{}/protection", encode_segment(branch));
+            match self.api(acct, &endpoint, None, None).await {
+                Ok(live) => changes.extend(plan_protection(branch, settings, Some(&live))),
+                Err(e) if e.is_not_found() => changes.extend(plan_protection(branch, settings, None)),
+                Err(e) => errors.push(format!("failed to read protection for '{branch}': {e}")),
+            }
+        }
+
+        if !spec.rulesets.is_empty() {
+            let endpoint = format!("repos/{repo}/rulesets");
+            if let Some(live) = self
+                .read_list(&account, &endpoint, "rulesets", &mut errors)
+                .await
+            {
+                for (name, settings) in &spec.rulesets {
+                    let summary = live.iter().find(|r| r["name"] == name.as_str());
+                    let detail = match summary {
+                        Some(summary) => match self.api(acct, &format!("{endpoint}/{}", summary["id"]), None, None).await {
+                            Ok(detail) => Some(detail),
+                            Err(e) => {
+                                errors.push(format!("failed to read ruleset '{name}': {e}"));
+                                continue;
+                            }
+                        },
+                        None => None,
+                    };
+                    match plan_ruleset(&repo, name, settings, detail.as_ref()) {
+                        Ok(change) => changes.extend(change),
+                        Err(e) => errors.push(format!("ruleset '{name}': {e}")),
+                    }
+                }
+            }
+        }
+
+        if let Some(ref labels) = spec.labels
+            && let Some(live) = self
+                .read_list(&account, &format!("repos/{repo}/labels"), "labels", &mut errors)
+                .await
+        {
+            changes.extend(plan_labels(&repo, labels, spec.prune_labels, &live));
+        }
+
+        if !spec.collaborators.is_empty() || spec.prune_collaborators {
+            let endpoint = format!("repos/{repo}/collaborators?affiliation=direct");
+            let collaborators = self.read_list(&account, &endpoint, "collaborators", &mut errors).await;
+            let invitations = self
+                .read_list(&account, &format!("repos/{repo}/invitations"), "invitations", &mut errors)
+                .await;
+            if let (Some(collaborators), Some(invitations)) = (collaborators, invitations) {
+                changes.extend(plan_collaborators(
+                    &repo,
+                    &spec.collaborators,
+                    spec.prune_collaborators,
+                    &collaborators,
+                    &invitations,
+                ));
+            }
+        }
+
+        if !spec.teams.is_empty()
+            && let Some(live) = self
+                .read_list(&account, &format!("repos/{repo}/teams"), "teams", &mut errors)
+                .await
+        {
+            changes.extend(plan_teams(&repo, &spec.teams, &live));
+        }
+
+        RepoPlan {
+            account,
+            repo,
+            changes,
+            errors,
+        }
+    }
+
+    /// Carry out planned changes, recording each failure on its change
+    pub async fn apply_policy(&self, plans: Vec<RepoPlan>) -> Vec<RepoPlan> {
+        let mut tasks = JoinSet::new();
+        for plan in plans {
+            let gh = self.clone();
+            tasks.spawn(async move { gh.apply_repo(plan).await });
+        }
+
+        let mut applied = Vec::new();
+        while let Some(joined) = tasks.join_next().await {
+            match joined {
+                Ok(plan) => applied.push(plan),
+                Err(e) => tracing::warn!("Policy apply task failed: {}", e),
+            }
+        }
+        applied.sort_by(|a, b| (&a.repo, &a.account).cmp(&(&b.repo, &b.account)));
+        applied
+    }
+
+    async fn apply_repo(&self, mut plan: RepoPlan) -> RepoPlan {
+        let account = Some(plan.account.as_str());
+        let (owner, name) = plan.repo.split_once('/').unwrap_or_default();
+        for change in &mut plan.changes {
+            let result = match change.operation {
+                Operation::Api {
+                    method,
+                    ref endpoint,
+                    body: Some(ref body),
+                } => self.api_json(account, endpoint, method, body).await,
+                Operation::Api {
+                    method,
+                    ref endpoint,
+                    body: None,
+                } => self.api(account, endpoint, Some(method), None).await,
+                Operation::Protect {
+                    ref branch,
+                    ref settings,
+                } => self.protect_branch(account, owner, name, branch, settings).await,
+            };
+            if let Err(e) = result {
+                change.error = Some(e.to_string());
+            }
+        }
+        plan
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_drift() {
+        let want = json!({"a": true, "b": null, "nested": {"x": 1, "list": ["ci", "lint"]}, "c": "x"});
+        let have = json!({"a": false, "b": 5, "nested": {"x": 1, "y": 2, "list": ["lint", "ci"]}});
+        let changes = drift(&want, &have);
+        assert_eq!(
+            Value::Object(changes),
+            json!({"a": {"from": false, "to": true}, "c": {"from": null, "to": "x"}})
+        );
+    }
+
+    #[test]
+    fn test_policy_spec_yaml() {
+        let yaml = r#"
+targets:
+  - account: work
+    repos: ["tatari-tv/*"]
+settings:
+  delete_branch_on_merge: true
+branch_protection:
+  main:
+    required_status_checks: {strict: true, contexts: [ci]}
+rulesets:
+  protect-tags:
+    target: tag
+    include_refs: ["v*"]
+    rules: [{type: deletion}]
+collaborators:
+  scottidler: write
+teams:
+  platform: maintain
+"#;
+        let spec: PolicySpec = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(spec.collaborators["scottidler"], Permission::Push);
+        assert!(serde_yaml::from_str::<PolicySpec>("setings: {}").is_err());
+
+        let spec = spec.with_targets(None, vec!["scottidler/gx".to_string()]).unwrap();
+        assert_eq!(spec.targets[0].repos, vec!["scottidler/gx"]);
+        assert!(PolicySpec::default().with_targets(None, Vec::new()).is_err());
+
+        let example = PolicySpec::load(concat!(env!("CARGO_MANIFEST_DIR"), "/policy.yml.example")).unwrap();
+        assert_eq!(example.targets.len(), 2);
+    }
+
+    #[test]
+    fn test_plan_settings_and_protection() {
+        let settings: RepoSettings =
+            serde_json::from_value(json!({"delete_branch_on_merge": true, "has_wiki": false})).unwrap();
+        let live = json!({"delete_branch_on_merge": false, "has_wiki": false, "name": "gx"});
+        let change = plan_settings("scottidler/gx", &settings, &live).unwrap();
+        assert_eq!(change.action, Action::Update);
+        assert_eq!(change.diff, json!({"delete_branch_on_merge": {"from": false, "to": true}}));
+        let Operation::Api { body, .. } = change.operation else {
+            panic!("expected an API call");
+        };
+        assert_eq!(body, Some(json!({"delete_branch_on_merge": true})));
+
+        let spec: BranchProtectionSettings =
+            serde_json::from_value(json!({"required_status_checks": {"contexts": ["ci"]}})).unwrap();
+        let live = json!({"required_status_checks": {"strict": true, "contexts": ["ci"]}, "enforce_admins": {"enabled": true}});
+        assert!(plan_protection("main", &spec, Some(&live)).is_none());
+        assert_eq!(plan_protection("main", &spec, None).unwrap().action, Action::Create);
+
+        let live = json!({
+            "required_status_checks": {"strict": true, "contexts": ["ci"]},
+            "restrictions": {"users": [], "teams": [{"slug": "core"}], "apps": []},
+            "required_conversation_resolution": {"enabled": true}
+        });
+        let spec: BranchProtectionSettings =
+            serde_json::from_value(json!({"required_status_checks": {"contexts": ["ci", "lint"]}})).unwrap();
+        let change = plan_protection("main", &spec, Some(&live)).unwrap();
+        assert_eq!(change.diff.as_object().unwrap().len(), 1);
+        let Operation::Protect { settings, .. } = change.operation else {
+            panic!("expected a protection change");
+        };
+        let body = settings.to_body();
+        assert_eq!(body["restrictions"]["teams"], json!(["core"]));
+        assert_eq!(body["required_conversation_resolution"], true);
+
+        let spec: BranchProtectionSettings = serde_json::from_value(json!({"restrictions": false})).unwrap();
+        let change = plan_protection("main", &spec, Some(&live)).unwrap();
+        assert_eq!(change.diff["restrictions"]["to"], Value::Null);
+    }
+
+    #[test]
+    fn test_plan_collaborators_and_teams() {
+        let spec = BTreeMap::from([
+            ("alice".to_string(), Permission::Push),
+            ("bob".to_string(), Permission::Admin),
+            ("carol".to_string(), Permission::Pull),
+        ]);
+        let collaborators = vec![
+            json!({"login": "scottidler", "role_name": "admin"}),
+            json!({"login": "Alice", "role_name": "write"}),
+            json!({"login": "bob", "role_name": "maintain"}),
+            json!({"login": "mallory", "role_name": "read"}),
+        ];
+        let invitations = vec![json!({"invitee": {"login": "carol"}, "permissions": "read"})];
+        let changes = plan_collaborators("scottidler/gx", &spec, true, &collaborators, &invitations);
+        let summary: Vec<(&str, Action)> = changes.iter().map(|c| (c.area.as_str(), c.action)).collect();
+        assert_eq!(
+            summary,
+            vec![("collaborator/bob", Action::Update), ("collaborator/mallory", Action::Delete)]
+        );
+
+        let teams = BTreeMap::from([("platform".to_string(), Permission::Maintain)]);
+        assert!(plan_teams("tatari-tv/svc", &teams, &[json!({"slug": "platform", "permission": "maintain"})]).is_empty());
+        let changes = plan_teams("tatari-tv/svc", &teams, &[]);
+        assert_eq!(changes[0].action, Action::Create);
+    }
+
+    #[test]
+    fn test_render_plan() {
+        let plans = vec![
+            RepoPlan {
+                account: "home".to_string(),
+                repo: "scottidler/gx".to_string(),
+                changes: vec![Change::api(
+                    "settings".to_string(),
+                    Action::Update,
+                    json!({"has_wiki": {"from": true, "to": false}}),
+                    "PATCH",
+                    "repos/scottidler/gx".to_string(),
+                    None,
+                )],
+                errors: Vec::new(),
+            },
+            RepoPlan {
+                account: "work".to_string(),
+                repo: "tatari-tv/svc".to_string(),
+                changes: Vec::new(),
+                errors: Vec::new(),
+            },
+        ];
+        assert_eq!(
+            render_plan(&plans),
+            "scottidler/gx (home)\n  ~ settings\n      has_wiki: true -> false\ntatari-tv/svc (work): no changes\n\n1 change(s) across 1 of 2 repositories\n"
+        );
+    }
+}
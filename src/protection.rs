@@ -0,0 +1,291 @@
+//! Applying branch protection: replacing it, patching it, and rolling it out across repositories
+
+use crate::gh::encode_segment;
+use crate::settings::BranchProtectionSettings;
+use crate::{GhClient, Result};
+use serde::Serialize;
+use serde_json::{Map, Value, json};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+/// A change to make to a branch's protection
+#[derive(Debug, Clone)]
+pub enum ProtectionChange {
+    /// Replace the protection; unset settings are disabled
+    Replace(BranchProtectionSettings),
+
+    /// Change the set settings and required status checks, keeping the rest
+    Update {
+        settings: BranchProtectionSettings,
+        add_status_checks: Vec<String>,
+        remove_status_checks: Vec<String>,
+    },
+}
+
+impl ProtectionChange {
+    /// The protection this change leaves on a branch whose current protection is `live`
+    /// (a `GET .../protection` response, None if the branch is unprotected)
+    pub fn apply_to(&self, live: Option<&Value>) -> BranchProtectionSettings {
+        match self {
+            ProtectionChange::Replace(settings) => settings.clone(),
+            ProtectionChange::Update {
+                settings,
+                add_status_checks,
+                remove_status_checks,
+            } => live
+                .map(BranchProtectionSettings::from_api)
+                .unwrap_or_default()
+                .merge(settings)
+                .with_status_checks(add_status_checks, remove_status_checks),
+        }
+    }
+}
+
+/// Outcome for one repository in a bulk rollout
+#[derive(Debug, Clone, Serialize)]
+pub struct BulkProtectionResult {
+    pub repo: String,
+
+    /// Branch the protection applies to, once resolved
+    pub branch: Option<String>,
+
+    /// applied, failed, planned or unchanged (dry run)
+    pub status: &'static str,
+
+    /// Dry run: the settings that would change, as `{field: {from, to}}`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diff: Option<Map<String, Value>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl GhClient {
+    /// Replace a branch's protection with `settings`, including the separate signed-commits setting
+    pub async fn protect_branch(
+        &self,
+        account: Option<&str>,
+        owner: &str,
+        repo: &str,
+        branch: &str,
+        settings: &BranchProtectionSettings,
+    ) -> Result<Value> {
+        let endpoint = format!("repos/{owner}/{repo}/branches/{}/protection", encode_segment(branch));
+        let mut result = self.api_json(account, &endpoint, "PUT", &settings.to_body()).await?;
+
+        if let Some(required) = settings.required_signatures {
+            let method = if required { "POST" } else { "DELETE" };
+            let signatures = self
+                .api(account, &format!("{endpoint}/required_signatures"), Some(method), None)
+                .await?;
+            result["required_signatures"] = if required { signatures } else { json!({ "enabled": false }) };
+        }
+        Ok(result)
+    }
+
+    /// Wait for a branch to appear, e.g. in a repository still being generated from a template.
+    ///
+    /// Polls up to `attempts` times, a second apart; returns whether the branch exists.
+    pub async fn wait_for_branch(
+        &self,
+        account: Option<&str>,
+        owner: &str,
+        repo: &str,
+        branch: &str,
+        attempts: u32,
+    ) -> Result<bool> {
+        let endpoint = format!("repos/{owner}/{repo}/branches/{}", encode_segment(branch));
+        for attempt in 0..attempts {
+            if attempt > 0 {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+            }
+            match self.api(account, &endpoint, None, None).await {
+                Ok(_) => return Ok(true),
+                Err(e) if e.is_not_found() => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(false)
+    }
+
+    /// Apply a protection change to a branch; updates read the current protection and merge into it
+    pub async fn change_protection(
+        &self,
+        account: Option<&str>,
+        owner: &str,
+        repo: &str,
+        branch: &str,
+        change: &ProtectionChange,
+    ) -> Result<Value> {
+        let live = match change {
+            ProtectionChange::Replace(_) => None,
+            ProtectionChange::Update { .. } => self.current_protection(account, owner, repo, branch).await?,
+        };
+        let settings = change.apply_to(live.as_ref());
+        self.protect_branch(account, owner, repo, branch, &settings).await
+    }
+
+    /// The settings a protection change would alter on a branch, as `{field: {from, to}}`
+    pub async fn preview_protection(
+        &self,
+        account: Option<&str>,
+        owner: &str,
+        repo: &str,
+        branch: &str,
+        change: &ProtectionChange,
+    ) -> Result<Map<String, Value>> {
+        let live = self.current_protection(account, owner, repo, branch).await?;
+        let current = live.as_ref().map(BranchProtectionSettings::from_api).unwrap_or_default();
+        Ok(current.diff(&change.apply_to(live.as_ref())))
+    }
+
+    /// A branch's current protection, or None if it isn't protected
+    pub async fn current_protection(
+        &self,
+        account: Option<&str>,
+        owner: &str,
+        repo: &str,
+        branch: &str,
+    ) -> Result<Option<Value>> {
+        let endpoint = format!("repos/{owner}/{repo}/branches/{}/protection", encode_segment(branch));
+        match self.api(account, &endpoint, None, None).await {
+            Ok(live) => Ok(Some(live)),
+            Err(e) if e.is_not_found() => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Apply a protection change to many repositories, at most `concurrency` at a time.
+    ///
+    /// `branch` defaults to each repository's default branch. Results are sorted by repository.
+    pub async fn change_protection_bulk(
+        &self,
+        account: Option<&str>,
+        repos: Vec<String>,
+        branch: Option<String>,
+        change: ProtectionChange,
+        concurrency: usize,
+        dry_run: bool,
+    ) -> Vec<BulkProtectionResult> {
+        let account = account.map(String::from);
+        let change = Arc::new(change);
+        let semaphore = Arc::new(Semaphore::new(concurrency.max(1)));
+
+        let mut tasks = JoinSet::new();
+        for repo in repos {
+            let gh = self.clone();
+            let account = account.clone();
+            let branch = branch.clone();
+            let change = change.clone();
+            let semaphore = semaphore.clone();
+            tasks.spawn(async move {
+                // The semaphore is never closed, so acquiring cannot fail
+                let _permit = semaphore.acquire_owned().await.ok();
+                gh.protect_one(account.as_deref(), repo, branch, &change, dry_run).await
+            });
+        }
+
+        let mut results = Vec::new();
+        while let Some(joined) = tasks.join_next().await {
+            match joined {
+                Ok(result) => results.push(result),
+                Err(e) => tracing::warn!("Bulk protection task failed: {}", e),
+            }
+        }
+        results.sort_by(|a, b| a.repo.cmp(&b.repo));
+        results
+    }
+
+    async fn protect_one(
+        &self,
+        account: Option<&str>,
+        repo: String,
+        branch: Option<String>,
+        change: &ProtectionChange,
+        dry_run: bool,
+    ) -> BulkProtectionResult {
+        let mut result = BulkProtectionResult {
+            repo,
+            branch,
+            status: "failed",
+            diff: None,
+            error: None,
+        };
+        let Some((owner, name)) = result.repo.split_once('/') else {
+            result.error = Some("expected owner/name".to_string());
+            return result;
+        };
+
+        let branch = match result.branch {
+            Some(ref branch) => branch.clone(),
+            None => match self.api(account, &format!("repos/{owner}/{name}"), None, None).await {
+                Ok(repo) => repo["default_branch"].as_str().unwrap_or("main").to_string(),
+                Err(e) => {
+                    result.error = Some(e.to_string());
+                    return result;
+                }
+            },
+        };
+
+        let outcome = if dry_run {
+            match self.preview_protection(account, owner, name, &branch, change).await {
+                Ok(diff) => {
+                    let status = if diff.is_empty() { "unchanged" } else { "planned" };
+                    result.diff = Some(diff);
+                    Ok(status)
+                }
+                Err(e) => Err(e),
+            }
+        } else {
+            self.change_protection(account, owner, name, &branch, change)
+                .await
+                .map(|_| "applied")
+        };
+        match outcome {
+            Ok(status) => result.status = status,
+            Err(e) => result.error = Some(e.to_string()),
+        }
+        result.branch = Some(branch);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_keeps_unmodelled_settings() {
+        let live = json!({
+            "required_status_checks": {"strict": true, "contexts": ["ci"], "checks": [{"context": "ci", "app_id": 15368}]},
+            "enforce_admins": {"enabled": true},
+            "restrictions": {"users": [{"login": "octocat"}], "teams": [{"slug": "core"}], "apps": []},
+            "required_conversation_resolution": {"enabled": true},
+            "allow_fork_syncing": {"enabled": true}
+        });
+        let change = ProtectionChange::Update {
+            settings: BranchProtectionSettings::default(),
+            add_status_checks: vec!["security-scan".to_string()],
+            remove_status_checks: Vec::new(),
+        };
+        let body = change.apply_to(Some(&live)).to_body();
+
+        assert_eq!(body["restrictions"], json!({"users": ["octocat"], "teams": ["core"], "apps": []}));
+        assert_eq!(body["required_conversation_resolution"], true);
+        assert_eq!(body["allow_fork_syncing"], true);
+        assert_eq!(body["enforce_admins"], true);
+        assert_eq!(
+            body["required_status_checks"]["checks"],
+            json!([{"context": "ci", "app_id": 15368}, {"context": "security-scan", "app_id": -1}])
+        );
+    }
+
+    #[test]
+    fn test_replace_ignores_live_protection() {
+        let live = json!({"required_conversation_resolution": {"enabled": true}});
+        let change = ProtectionChange::Replace(BranchProtectionSettings::default());
+        assert!(change.apply_to(Some(&live)).required_conversation_resolution.is_none());
+    }
+}
@@ -0,0 +1,183 @@
+//! Secret values: redacted formatting, sealed-box encryption, and log and output redaction
+
+use crate::{Error, Result};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use crypto_box::PublicKey;
+use crypto_box::aead::OsRng;
+use rmcp::schemars;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::Value;
+use std::fmt;
+use tracing::level_filters::LevelFilter;
+
+/// Tracing directive that keeps rmcp from logging full requests, which would include secret values
+pub const REQUEST_LOG_DIRECTIVE: &str = "rmcp::service=info";
+
+/// Tracing filter for a configured log level, capping rmcp request logging at info.
+///
+/// The cap is only added when the base level is more verbose than info, so quieter
+/// levels such as `warn` stay quiet.
+pub fn log_filter(level: &str) -> String {
+    let verbose = level
+        .split(',')
+        .filter(|directive| !directive.contains('='))
+        .filter_map(|directive| directive.trim().parse::<LevelFilter>().ok())
+        .any(|base| base > LevelFilter::INFO);
+    if verbose {
+        format!("{level},{REQUEST_LOG_DIRECTIVE}")
+    } else {
+        level.to_string()
+    }
+}
+
+/// A plaintext secret that is never printed.
+///
+/// `Debug` and `Display` are redacted and the type is deliberately not `Serialize`, so it
+/// can't end up in logs or tool results by accident.
+#[derive(Clone, Deserialize, JsonSchema)]
+#[serde(transparent)]
+pub struct SecretString(String);
+
+impl SecretString {
+    /// Access the plaintext, e.g. to encrypt it
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for SecretString {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl fmt::Debug for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretString([REDACTED])")
+    }
+}
+
+impl fmt::Display for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[REDACTED]")
+    }
+}
+
+/// Encrypt a secret for GitHub with a libsodium sealed box.
+///
+/// `public_key` is the base64 key from a `.../secrets/public-key` endpoint; the result is
+/// the base64 `encrypted_value` to send with that key's `key_id`.
+pub fn seal(public_key: &str, secret: &SecretString) -> Result<String> {
+    let key = STANDARD
+        .decode(public_key)
+        .map_err(|e| Error::Tool(format!("invalid secrets public key: {e}")))?;
+    let key = PublicKey::from_slice(&key).map_err(|_| Error::Tool("invalid secrets public key length".to_string()))?;
+    let sealed = key
+        .seal(&mut OsRng, secret.expose().as_bytes())
+        .map_err(|_| Error::Tool("failed to encrypt secret".to_string()))?;
+    Ok(STANDARD.encode(sealed))
+}
+
+/// Field names whose values are masked in logged gh arguments
+const SENSITIVE_FIELDS: &[&str] = &["value", "encrypted_value", "secret", "token", "password", "key"];
+
+/// Join gh arguments for logging, masking sensitive `-f key=value` fields and auth headers
+pub fn redact_args(args: &[&str]) -> String {
+    let mut redacted = Vec::with_capacity(args.len());
+    let mut previous = "";
+    for arg in args {
+        let masked = match previous {
+            "-f" | "-F" | "--field" | "--raw-field" => match arg.split_once('=') {
+                Some((key, _)) if SENSITIVE_FIELDS.iter().any(|s| key.to_lowercase().contains(s)) => {
+                    format!("{key}=[REDACTED]")
+                }
+                _ => arg.to_string(),
+            },
+            "-H" | "--header" if arg.to_lowercase().starts_with("authorization:") => "Authorization: [REDACTED]".to_string(),
+            _ => arg.to_string(),
+        };
+        redacted.push(masked);
+        previous = arg;
+    }
+    redacted.join(" ")
+}
+
+/// Replace the value of every `secret` field in `value`, at any depth, with `[REDACTED]`
+pub fn redact_secrets(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            for (key, field) in object.iter_mut() {
+                if key.eq_ignore_ascii_case("secret") && !field.is_null() {
+                    *field = Value::String("[REDACTED]".to_string());
+                } else {
+                    redact_secrets(field);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_secrets),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crypto_box::SecretKey;
+
+    #[test]
+    fn test_secret_string_redacted() {
+        let secret: SecretString = serde_json::from_str(r#""hunter2""#).unwrap();
+        assert_eq!(secret.expose(), "hunter2");
+        assert_eq!(format!("{secret:?}"), "SecretString([REDACTED])");
+        assert_eq!(secret.to_string(), "[REDACTED]");
+    }
+
+    #[test]
+    fn test_seal_roundtrip() {
+        let secret_key = SecretKey::generate(&mut OsRng);
+        let public_key = STANDARD.encode(secret_key.public_key().as_bytes());
+
+        let sealed = seal(&public_key, &SecretString::from("hunter2".to_string())).unwrap();
+        let opened = secret_key.unseal(&STANDARD.decode(sealed).unwrap()).unwrap();
+        assert_eq!(opened, b"hunter2");
+
+        assert!(seal("not base64!", &SecretString::from("x".to_string())).is_err());
+        assert!(seal(&STANDARD.encode([0u8; 8]), &SecretString::from("x".to_string())).is_err());
+    }
+
+    #[test]
+    fn test_redact_args() {
+        let args = ["api", "-X", "PUT", "repos/o/r/actions/variables/X", "-f", "name=X", "-f", "value=s3cret"];
+        assert_eq!(
+            redact_args(&args),
+            "api -X PUT repos/o/r/actions/variables/X -f name=X -f value=[REDACTED]"
+        );
+        assert_eq!(
+            redact_args(&["api", "user", "-H", "Authorization: token abc"]),
+            "api user -H Authorization: [REDACTED]"
+        );
+    }
+
+    #[test]
+    fn test_redact_secrets() {
+        let mut hooks = serde_json::json!([
+            {"id": 1, "config": {"url": "https://ci.example.com", "secret": "********"}},
+            {"id": 2, "config": {"url": "https://ci.example.com", "secret": null}}
+        ]);
+        redact_secrets(&mut hooks);
+        assert_eq!(hooks[0]["config"]["secret"], "[REDACTED]");
+        assert!(hooks[1]["config"]["secret"].is_null());
+        assert_eq!(hooks[0]["config"]["url"], "https://ci.example.com");
+    }
+
+    #[test]
+    fn test_log_filter() {
+        assert_eq!(log_filter("debug"), "debug,rmcp::service=info");
+        assert_eq!(log_filter("trace,hyper=warn"), "trace,hyper=warn,rmcp::service=info");
+        assert_eq!(log_filter("info"), "info");
+        assert_eq!(log_filter("warn"), "warn");
+        assert_eq!(log_filter("error"), "error");
+    }
+}
@@ -0,0 +1,658 @@
+//! Repository and branch protection setting models shared by config, policy and tools
+
+use rmcp::schemars;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
+
+/// Repository settings accepted by the repos API; unset fields are left unchanged
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+pub struct RepoSettings {
+    /// Short description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Short description of the repository")]
+    pub description: Option<String>,
+
+    /// Homepage URL
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Homepage URL")]
+    pub homepage: Option<String>,
+
+    /// Visibility
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Visibility: public, private or internal")]
+    pub visibility: Option<String>,
+
+    /// Default branch
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Default branch (must already exist)")]
+    pub default_branch: Option<String>,
+
+    /// Enable issues
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Enable issues")]
+    pub has_issues: Option<bool>,
+
+    /// Enable the wiki
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Enable the wiki")]
+    pub has_wiki: Option<bool>,
+
+    /// Enable projects
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Enable projects")]
+    pub has_projects: Option<bool>,
+
+    /// Enable discussions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Enable discussions")]
+    pub has_discussions: Option<bool>,
+
+    /// Allow squash merging
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Allow squash merging pull requests")]
+    pub allow_squash_merge: Option<bool>,
+
+    /// Allow merge commits
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Allow merging pull requests with a merge commit")]
+    pub allow_merge_commit: Option<bool>,
+
+    /// Allow rebase merging
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Allow rebase merging pull requests")]
+    pub allow_rebase_merge: Option<bool>,
+
+    /// Allow auto-merge
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Allow auto-merge on pull requests")]
+    pub allow_auto_merge: Option<bool>,
+
+    /// Delete head branches after merge
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Automatically delete head branches after pull requests are merged")]
+    pub delete_branch_on_merge: Option<bool>,
+
+    /// Suggest updating PR branches
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Always suggest updating pull request branches that are behind the base")]
+    pub allow_update_branch: Option<bool>,
+
+    /// Template repository
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Make the repository available as a template")]
+    pub is_template: Option<bool>,
+}
+
+/// A required status check, optionally bound to the GitHub App that must report it
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub struct StatusCheck {
+    /// Check name
+    #[schemars(description = "Name of the status check")]
+    pub context: String,
+
+    /// App that must report the check
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "ID of the GitHub App that must report the check; -1 or unset allows any app")]
+    pub app_id: Option<i64>,
+}
+
+/// Required status checks configuration
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+pub struct RequiredStatusChecks {
+    /// Require branches to be up to date before merging
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Require branches to be up to date before merging")]
+    pub strict: Option<bool>,
+
+    /// List of status check contexts that must pass
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "List of status check contexts that must pass")]
+    pub contexts: Option<Vec<String>>,
+
+    /// Checks with the app that must report them
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        description = "Required checks with the GitHub App that must report each. When contexts is also set, contexts decides which checks are required and these only supply app_ids."
+    )]
+    pub checks: Option<Vec<StatusCheck>>,
+}
+
+impl RequiredStatusChecks {
+    /// The required check names: `contexts` if set, otherwise the names in `checks`
+    pub fn names(&self) -> Vec<String> {
+        match (&self.contexts, &self.checks) {
+            (Some(contexts), _) => contexts.clone(),
+            (None, Some(checks)) => checks.iter().map(|check| check.context.clone()).collect(),
+            (None, None) => Vec::new(),
+        }
+    }
+
+    fn app_id(&self, context: &str) -> Option<i64> {
+        self.checks
+            .iter()
+            .flatten()
+            .find(|check| check.context == context)
+            .and_then(|check| check.app_id)
+    }
+
+    /// Body sent as `required_status_checks`; `checks` is used only when some check is bound to an app
+    fn to_body(&self) -> Value {
+        let names = self.names();
+        let strict = self.strict.unwrap_or(false);
+        if names.iter().all(|name| self.app_id(name).is_none()) {
+            return json!({ "strict": strict, "contexts": names });
+        }
+        let checks: Vec<Value> = names
+            .iter()
+            .map(|name| json!({ "context": name, "app_id": self.app_id(name).unwrap_or(-1) }))
+            .collect();
+        json!({ "strict": strict, "checks": checks })
+    }
+}
+
+/// Users, teams and apps singled out by a protection setting
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub struct ProtectionActors {
+    /// User logins
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[schemars(description = "User logins")]
+    pub users: Vec<String>,
+
+    /// Team slugs
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[schemars(description = "Team slugs")]
+    pub teams: Vec<String>,
+
+    /// App slugs
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[schemars(description = "GitHub App slugs")]
+    pub apps: Vec<String>,
+}
+
+impl ProtectionActors {
+    /// Read actors from an API object with `users`, `teams` and `apps` arrays; None if it isn't an object
+    fn from_api(value: &Value) -> Option<Self> {
+        let names = |key: &str, field: &str| -> Vec<String> {
+            value[key]
+                .as_array()
+                .map(|items| items.iter().filter_map(|item| item[field].as_str().map(String::from)).collect())
+                .unwrap_or_default()
+        };
+        value.is_object().then(|| Self {
+            users: names("users", "login"),
+            teams: names("teams", "slug"),
+            apps: names("apps", "slug"),
+        })
+    }
+
+    fn to_body(&self) -> Value {
+        json!({ "users": self.users, "teams": self.teams, "apps": self.apps })
+    }
+}
+
+/// Who may push to a protected branch
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema)]
+#[serde(untagged)]
+pub enum PushRestrictions {
+    /// `true` lets only administrators push; `false` lifts the restriction
+    Enabled(bool),
+
+    /// Only these users, teams and apps (and administrators) may push
+    Only(ProtectionActors),
+}
+
+/// Required pull request reviews configuration
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+pub struct RequiredPullRequestReviews {
+    /// Number of required approving reviews
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Number of required approving reviews")]
+    pub required_approving_review_count: Option<u32>,
+
+    /// Dismiss stale reviews when new commits are pushed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Dismiss stale reviews when new commits are pushed")]
+    pub dismiss_stale_reviews: Option<bool>,
+
+    /// Require review from code owners
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Require review from code owners")]
+    pub require_code_owner_reviews: Option<bool>,
+
+    /// Require approval of the most recent push by someone else
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Require the most recent push to be approved by someone other than the pusher")]
+    pub require_last_push_approval: Option<bool>,
+
+    /// Who may dismiss reviews
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Only these users, teams and apps may dismiss reviews (organization repositories)")]
+    pub dismissal_restrictions: Option<ProtectionActors>,
+
+    /// Who may skip the review requirement
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Users, teams and apps allowed to bypass the pull request requirement")]
+    pub bypass_pull_request_allowances: Option<ProtectionActors>,
+}
+
+impl RequiredPullRequestReviews {
+    fn to_body(&self) -> Value {
+        let mut body = json!({
+            "required_approving_review_count": self.required_approving_review_count.unwrap_or(1),
+            "dismiss_stale_reviews": self.dismiss_stale_reviews.unwrap_or(false),
+            "require_code_owner_reviews": self.require_code_owner_reviews.unwrap_or(false),
+            "require_last_push_approval": self.require_last_push_approval.unwrap_or(false),
+        });
+        if let Some(ref actors) = self.dismissal_restrictions {
+            body["dismissal_restrictions"] = actors.to_body();
+        }
+        if let Some(ref actors) = self.bypass_pull_request_allowances {
+            body["bypass_pull_request_allowances"] = actors.to_body();
+        }
+        body
+    }
+}
+
+/// Branch protection settings, as accepted by set_branch_protection and repo profiles
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+pub struct BranchProtectionSettings {
+    /// Require status checks to pass before merging
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Require status checks to pass before merging")]
+    pub required_status_checks: Option<RequiredStatusChecks>,
+
+    /// Enforce all configured restrictions for administrators
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Enforce all configured restrictions for administrators")]
+    pub enforce_admins: Option<bool>,
+
+    /// Require pull request reviews before merging
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Require pull request reviews before merging")]
+    pub required_pull_request_reviews: Option<RequiredPullRequestReviews>,
+
+    /// Restrict who can push to the protected branch
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        description = "Restrict who can push: true for administrators only, false for no restriction, or {users, teams, apps} allowed to push (organization repositories)"
+    )]
+    pub restrictions: Option<PushRestrictions>,
+
+    /// Require signed commits
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Require signed commits")]
+    pub required_signatures: Option<bool>,
+
+    /// Require linear history
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Require linear history (no merge commits)")]
+    pub required_linear_history: Option<bool>,
+
+    /// Allow force pushes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Allow force pushes")]
+    pub allow_force_pushes: Option<bool>,
+
+    /// Allow deletions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Allow branch deletions")]
+    pub allow_deletions: Option<bool>,
+
+    /// Require conversation resolution
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Require all review conversations to be resolved before merging")]
+    pub required_conversation_resolution: Option<bool>,
+
+    /// Lock the branch
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Make the branch read-only")]
+    pub lock_branch: Option<bool>,
+
+    /// Block branch creation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Block creating branches that match the protection rule")]
+    pub block_creations: Option<bool>,
+
+    /// Allow fork syncing
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Allow users to pull changes from upstream into a locked branch's forks")]
+    pub allow_fork_syncing: Option<bool>,
+}
+
+impl BranchProtectionSettings {
+    /// Request body for `PUT repos/{owner}/{repo}/branches/{branch}/protection`.
+    ///
+    /// The endpoint requires `required_status_checks`, `enforce_admins`,
+    /// `required_pull_request_reviews` and `restrictions`; unset ones are sent as disabled.
+    /// `required_signatures` has its own endpoint and is not part of the body.
+    pub fn to_body(&self) -> Value {
+        let restrictions = match self.restrictions {
+            None | Some(PushRestrictions::Enabled(false)) => Value::Null,
+            Some(PushRestrictions::Enabled(true)) => ProtectionActors::default().to_body(),
+            Some(PushRestrictions::Only(ref actors)) => actors.to_body(),
+        };
+        let mut body = json!({
+            "required_status_checks": self.required_status_checks.as_ref().map(RequiredStatusChecks::to_body),
+            "enforce_admins": self.enforce_admins.unwrap_or(false),
+            "required_pull_request_reviews": self
+                .required_pull_request_reviews
+                .as_ref()
+                .map(RequiredPullRequestReviews::to_body),
+            "restrictions": restrictions,
+        });
+
+        for (key, value) in [
+            ("required_linear_history", self.required_linear_history),
+            ("allow_force_pushes", self.allow_force_pushes),
+            ("allow_deletions", self.allow_deletions),
+            ("required_conversation_resolution", self.required_conversation_resolution),
+            ("lock_branch", self.lock_branch),
+            ("block_creations", self.block_creations),
+            ("allow_fork_syncing", self.allow_fork_syncing),
+        ] {
+            if let Some(value) = value {
+                body[key] = json!(value);
+            }
+        }
+        body
+    }
+
+    /// Settings that differ between these and `to`, as `{field: {from, to}}`.
+    ///
+    /// Compares the protection each would actually apply, so settings that `to` turns off show up too.
+    pub fn diff(&self, to: &Self) -> Map<String, Value> {
+        let effective = |settings: &Self| {
+            let mut body = settings.to_body();
+            body["required_signatures"] = json!(settings.required_signatures.unwrap_or(false));
+            for key in [
+                "required_linear_history",
+                "allow_force_pushes",
+                "allow_deletions",
+                "required_conversation_resolution",
+                "lock_branch",
+                "block_creations",
+                "allow_fork_syncing",
+            ] {
+                if body[key].is_null() {
+                    body[key] = json!(false);
+                }
+            }
+            body
+        };
+        let mut changes = Map::new();
+        diff_into(&mut changes, "", &effective(self), &effective(to));
+        changes
+    }
+}
+
+/// Record every field that differs between `from` and `to` under its dotted path
+fn diff_into(changes: &mut Map<String, Value>, path: &str, from: &Value, to: &Value) {
+    if let (Some(from_fields), Some(to_fields)) = (from.as_object(), to.as_object()) {
+        let mut keys: Vec<&String> = from_fields.keys().chain(to_fields.keys()).collect();
+        keys.sort();
+        keys.dedup();
+        for key in keys {
+            let nested = if path.is_empty() { key.clone() } else { format!("{path}.{key}") };
+            let from = from_fields.get(key).unwrap_or(&Value::Null);
+            let to = to_fields.get(key).unwrap_or(&Value::Null);
+            diff_into(changes, &nested, from, to);
+        }
+    } else if !same(from, to) {
+        changes.insert(path.to_string(), json!({ "from": from, "to": to }));
+    }
+}
+
+/// Whether two values are equal, comparing arrays without regard to order
+fn same(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Array(a), Value::Array(b)) => a.len() == b.len() && a.iter().all(|x| b.iter().any(|y| same(x, y))),
+        _ => a == b,
+    }
+}
+
+impl BranchProtectionSettings {
+    /// Read settings from a `GET .../branches/{branch}/protection` response
+    pub fn from_api(protection: &Value) -> Self {
+        let enabled = |key: &str| protection[key]["enabled"].as_bool();
+        let checks = &protection["required_status_checks"];
+        let reviews = &protection["required_pull_request_reviews"];
+
+        Self {
+            required_status_checks: checks.is_object().then(|| RequiredStatusChecks {
+                strict: checks["strict"].as_bool(),
+                contexts: checks["contexts"]
+                    .as_array()
+                    .map(|contexts| contexts.iter().filter_map(|c| c.as_str().map(String::from)).collect()),
+                checks: checks["checks"].as_array().map(|checks| {
+                    checks
+                        .iter()
+                        .filter_map(|check| {
+                            Some(StatusCheck {
+                                context: check["context"].as_str()?.to_string(),
+                                app_id: check["app_id"].as_i64(),
+                            })
+                        })
+                        .collect()
+                }),
+            }),
+            enforce_admins: enabled("enforce_admins"),
+            required_pull_request_reviews: reviews.is_object().then(|| RequiredPullRequestReviews {
+                required_approving_review_count: reviews["required_approving_review_count"]
+                    .as_u64()
+                    .map(|count| count as u32),
+                dismiss_stale_reviews: reviews["dismiss_stale_reviews"].as_bool(),
+                require_code_owner_reviews: reviews["require_code_owner_reviews"].as_bool(),
+                require_last_push_approval: reviews["require_last_push_approval"].as_bool(),
+                dismissal_restrictions: ProtectionActors::from_api(&reviews["dismissal_restrictions"]),
+                bypass_pull_request_allowances: ProtectionActors::from_api(&reviews["bypass_pull_request_allowances"]),
+            }),
+            restrictions: protection.is_object().then(|| {
+                ProtectionActors::from_api(&protection["restrictions"])
+                    .map_or(PushRestrictions::Enabled(false), PushRestrictions::Only)
+            }),
+            required_signatures: enabled("required_signatures"),
+            required_linear_history: enabled("required_linear_history"),
+            allow_force_pushes: enabled("allow_force_pushes"),
+            allow_deletions: enabled("allow_deletions"),
+            required_conversation_resolution: enabled("required_conversation_resolution"),
+            lock_branch: enabled("lock_branch"),
+            block_creations: enabled("block_creations"),
+            allow_fork_syncing: enabled("allow_fork_syncing"),
+        }
+    }
+
+    /// These settings with every field set in `patch` overridden, including nested fields
+    pub fn merge(&self, patch: &Self) -> Self {
+        let checks = match (&self.required_status_checks, &patch.required_status_checks) {
+            (Some(base), Some(patch)) => Some(RequiredStatusChecks {
+                strict: patch.strict.or(base.strict),
+                contexts: patch.contexts.clone().or_else(|| base.contexts.clone()),
+                checks: patch.checks.clone().or_else(|| base.checks.clone()),
+            }),
+            (base, patch) => patch.clone().or_else(|| base.clone()),
+        };
+        let reviews = match (&self.required_pull_request_reviews, &patch.required_pull_request_reviews) {
+            (Some(base), Some(patch)) => Some(RequiredPullRequestReviews {
+                required_approving_review_count: patch
+                    .required_approving_review_count
+                    .or(base.required_approving_review_count),
+                dismiss_stale_reviews: patch.dismiss_stale_reviews.or(base.dismiss_stale_reviews),
+                require_code_owner_reviews: patch.require_code_owner_reviews.or(base.require_code_owner_reviews),
+                require_last_push_approval: patch.require_last_push_approval.or(base.require_last_push_approval),
+                dismissal_restrictions: patch
+                    .dismissal_restrictions
+                    .clone()
+                    .or_else(|| base.dismissal_restrictions.clone()),
+                bypass_pull_request_allowances: patch
+                    .bypass_pull_request_allowances
+                    .clone()
+                    .or_else(|| base.bypass_pull_request_allowances.clone()),
+            }),
+            (base, patch) => patch.clone().or_else(|| base.clone()),
+        };
+
+        Self {
+            required_status_checks: checks,
+            enforce_admins: patch.enforce_admins.or(self.enforce_admins),
+            required_pull_request_reviews: reviews,
+            restrictions: patch.restrictions.clone().or_else(|| self.restrictions.clone()),
+            required_signatures: patch.required_signatures.or(self.required_signatures),
+            required_linear_history: patch.required_linear_history.or(self.required_linear_history),
+            allow_force_pushes: patch.allow_force_pushes.or(self.allow_force_pushes),
+            allow_deletions: patch.allow_deletions.or(self.allow_deletions),
+            required_conversation_resolution: patch
+                .required_conversation_resolution
+                .or(self.required_conversation_resolution),
+            lock_branch: patch.lock_branch.or(self.lock_branch),
+            block_creations: patch.block_creations.or(self.block_creations),
+            allow_fork_syncing: patch.allow_fork_syncing.or(self.allow_fork_syncing),
+        }
+    }
+
+    /// Add and remove required status check contexts, enabling required checks if needed
+    pub fn with_status_checks(mut self, add: &[String], remove: &[String]) -> Self {
+        if add.is_empty() && remove.is_empty() {
+            return self;
+        }
+        let checks = self.required_status_checks.get_or_insert_with(RequiredStatusChecks::default);
+        let mut contexts = checks.names();
+        for context in add {
+            if !contexts.contains(context) {
+                contexts.push(context.clone());
+            }
+        }
+        contexts.retain(|context| !remove.contains(context));
+        checks.contexts = Some(contexts);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_branch_protection_body() {
+        let json = r#"{
+            "required_status_checks": {"strict": true, "contexts": ["ci"]},
+            "required_linear_history": true,
+            "required_signatures": true
+        }"#;
+        let settings: BranchProtectionSettings = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            settings.to_body(),
+            json!({
+                "required_status_checks": {"strict": true, "contexts": ["ci"]},
+                "enforce_admins": false,
+                "required_pull_request_reviews": null,
+                "restrictions": null,
+                "required_linear_history": true
+            })
+        );
+    }
+
+    #[test]
+    fn test_branch_protection_from_api_and_merge() {
+        let live = json!({
+            "required_status_checks": {"strict": false, "contexts": ["ci"], "checks": [{"context": "ci"}]},
+            "enforce_admins": {"enabled": true},
+            "required_pull_request_reviews": {"required_approving_review_count": 1, "dismiss_stale_reviews": false},
+            "required_signatures": {"enabled": false},
+            "allow_force_pushes": {"enabled": false}
+        });
+        let live = BranchProtectionSettings::from_api(&live);
+        assert_eq!(live.enforce_admins, Some(true));
+        assert_eq!(live.restrictions, Some(PushRestrictions::Enabled(false)));
+        assert_eq!(live.required_linear_history, None);
+
+        let patch: BranchProtectionSettings = serde_json::from_str(
+            r#"{"required_status_checks": {"strict": true}, "required_pull_request_reviews": {"required_approving_review_count": 2}}"#,
+        )
+        .unwrap();
+        let merged = live.merge(&patch);
+        let checks = merged.required_status_checks.unwrap();
+        assert_eq!(checks.strict, Some(true));
+        assert_eq!(checks.contexts, Some(vec!["ci".to_string()]));
+        let reviews = merged.required_pull_request_reviews.unwrap();
+        assert_eq!(reviews.required_approving_review_count, Some(2));
+        assert_eq!(reviews.dismiss_stale_reviews, Some(false));
+        assert_eq!(merged.enforce_admins, Some(true));
+    }
+
+    #[test]
+    fn test_with_status_checks() {
+        let settings = BranchProtectionSettings::default().with_status_checks(&["ci".to_string()], &[]);
+        assert_eq!(
+            serde_json::to_value(&settings).unwrap(),
+            json!({"required_status_checks": {"contexts": ["ci"]}})
+        );
+
+        let settings = settings.with_status_checks(&["lint".to_string(), "ci".to_string()], &["ci".to_string()]);
+        assert_eq!(settings.required_status_checks.unwrap().contexts, Some(vec!["lint".to_string()]));
+    }
+
+    #[test]
+    fn test_branch_protection_round_trip() {
+        let live = json!({
+            "required_status_checks": {
+                "strict": true,
+                "contexts": ["ci", "lint"],
+                "checks": [{"context": "ci", "app_id": 15368}, {"context": "lint", "app_id": null}]
+            },
+            "enforce_admins": {"enabled": false},
+            "required_pull_request_reviews": {
+                "required_approving_review_count": 2,
+                "require_last_push_approval": true,
+                "dismissal_restrictions": {"users": [{"login": "octocat"}], "teams": [], "apps": []},
+                "bypass_pull_request_allowances": {"users": [], "teams": [{"slug": "release"}], "apps": []}
+            },
+            "restrictions": {"users": [], "teams": [{"slug": "core"}], "apps": [{"slug": "deploy-bot"}]},
+            "required_conversation_resolution": {"enabled": true},
+            "lock_branch": {"enabled": false}
+        });
+        let body = BranchProtectionSettings::from_api(&live).to_body();
+        assert_eq!(
+            body["required_status_checks"],
+            json!({"strict": true, "checks": [{"context": "ci", "app_id": 15368}, {"context": "lint", "app_id": -1}]})
+        );
+        assert_eq!(body["restrictions"], json!({"users": [], "teams": ["core"], "apps": ["deploy-bot"]}));
+        let reviews = &body["required_pull_request_reviews"];
+        assert_eq!(reviews["require_last_push_approval"], true);
+        assert_eq!(reviews["dismissal_restrictions"]["users"], json!(["octocat"]));
+        assert_eq!(reviews["bypass_pull_request_allowances"]["teams"], json!(["release"]));
+        assert_eq!(body["required_conversation_resolution"], true);
+        assert_eq!(body["lock_branch"], false);
+    }
+
+    #[test]
+    fn test_push_restrictions_accepts_bool() {
+        let settings: BranchProtectionSettings = serde_json::from_str(r#"{"restrictions": true}"#).unwrap();
+        assert_eq!(settings.to_body()["restrictions"], json!({"users": [], "teams": [], "apps": []}));
+        let settings: BranchProtectionSettings =
+            serde_json::from_str(r#"{"restrictions": {"teams": ["core"]}}"#).unwrap();
+        assert_eq!(settings.to_body()["restrictions"]["teams"], json!(["core"]));
+    }
+
+    #[test]
+    fn test_branch_protection_diff() {
+        let live = BranchProtectionSettings::from_api(&json!({
+            "required_status_checks": {"strict": false, "contexts": ["ci"]},
+            "enforce_admins": {"enabled": false},
+            "restrictions": {"users": [{"login": "octocat"}], "teams": [], "apps": []},
+            "required_conversation_resolution": {"enabled": true}
+        }));
+        let patch: BranchProtectionSettings = serde_json::from_value(json!({
+            "required_status_checks": {"contexts": ["ci", "lint"]},
+            "restrictions": false
+        }))
+        .unwrap();
+        let target = live.merge(&patch);
+
+        let diff = live.diff(&target);
+        assert_eq!(diff["required_status_checks.contexts"]["to"], json!(["ci", "lint"]));
+        assert_eq!(diff["restrictions"]["to"], Value::Null);
+        assert_eq!(diff.len(), 2);
+        assert!(live.diff(&live).is_empty());
+    }
+}
@@ -1,8 +1,11 @@
-//! Account-related tools (get_me)
+//! Account-related tools (get_me, my_dashboard)
 
+use crate::output::OutputOptions;
+use crate::tools::search::SearchKind;
 use rmcp::schemars;
 use schemars::JsonSchema;
 use serde::Deserialize;
+use serde_json::{Map, Value, json};
 
 /// Request parameters for get_me tool
 #[derive(Debug, Deserialize, JsonSchema)]
@@ -10,6 +13,82 @@ pub struct GetMeRequest {
     /// The account to use (e.g., "home", "work"). Uses default account if not specified.
     #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
     pub account: Option<String>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+/// Request parameters for my_dashboard tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct MyDashboardRequest {
+    /// Maximum number of items per section and account
+    #[schemars(description = "Maximum number of items per section and account (default: 20)")]
+    pub limit: Option<u32>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+/// Dashboard sections: name, what to search and the query (`@me` resolves per account)
+pub const DASHBOARD_SECTIONS: [(&str, SearchKind, &str); 4] = [
+    ("authored_prs", SearchKind::Prs, "is:open author:@me"),
+    ("review_requested", SearchKind::Prs, "is:open review-requested:@me"),
+    ("assigned_issues", SearchKind::Issues, "is:open assignee:@me"),
+    ("failing_prs", SearchKind::Prs, "is:open author:@me status:failure"),
+];
+
+/// Compact an item from a PR or issue search
+pub fn dashboard_item(item: &Value) -> Value {
+    json!({
+        "repo": item["repository"]["nameWithOwner"],
+        "number": item["number"],
+        "title": item["title"],
+        "updated_at": item["updatedAt"],
+        "url": item["url"],
+    })
+}
+
+/// Flatten per-account dashboards into one item list and add a per-account count summary.
+///
+/// Each account's value is an object with `login`, one array per section and, if any section
+/// failed, an `errors` object keyed by section. Items are listed under `items` tagged with their
+/// account and section, so the response budget trims items rather than dropping the summary.
+/// Accounts that failed entirely are listed under `errors`.
+pub fn shape_dashboard(per_account: Vec<(String, crate::Result<Value>)>) -> Value {
+    let mut summary = Map::new();
+    let mut items = Vec::new();
+    let mut errors = Map::new();
+
+    for (account, result) in per_account {
+        match result {
+            Ok(mut dashboard) => {
+                let mut counts = Map::new();
+                counts.insert("login".to_string(), dashboard["login"].take());
+                for (name, _, _) in DASHBOARD_SECTIONS {
+                    let Value::Array(section) = dashboard[name].take() else {
+                        continue;
+                    };
+                    counts.insert(name.to_string(), json!(section.len()));
+                    items.extend(section.into_iter().map(|mut item| {
+                        item["account"] = json!(account);
+                        item["section"] = json!(name);
+                        item
+                    }));
+                }
+                if let Some(section_errors) = dashboard.get_mut("errors") {
+                    counts.insert("errors".to_string(), section_errors.take());
+                }
+                summary.insert(account, Value::Object(counts));
+            }
+            Err(e) => {
+                errors.insert(account, json!(e.to_string()));
+            }
+        }
+    }
+
+    json!({ "summary": summary, "items": items, "errors": errors })
 }
 
 #[cfg(test)]
@@ -29,4 +108,41 @@ mod tests {
         let request: GetMeRequest = serde_json::from_str(json).unwrap();
         assert_eq!(request.account, None);
     }
+
+    #[test]
+    fn test_shape_dashboard() {
+        let home = json!({
+            "login": "scottidler",
+            "authored_prs": [dashboard_item(&json!({"repository": {"nameWithOwner": "scottidler/gx"}, "number": 1, "title": "a", "url": "u"}))],
+            "review_requested": [],
+            "assigned_issues": [],
+            "failing_prs": []
+        });
+        let oss = json!({
+            "login": "scottidler-oss",
+            "authored_prs": [],
+            "review_requested": [dashboard_item(&json!({"repository": {"nameWithOwner": "oss/tool"}, "number": 7}))],
+            "assigned_issues": [],
+            "errors": {"failing_prs": "gh CLI error: HTTP 502"}
+        });
+        let dashboard = shape_dashboard(vec![
+            ("home".to_string(), Ok(home)),
+            ("oss".to_string(), Ok(oss)),
+            ("work".to_string(), Err(crate::Error::GhCli("HTTP 401".to_string()))),
+        ]);
+
+        assert_eq!(dashboard["summary"]["home"]["login"], "scottidler");
+        assert_eq!(dashboard["summary"]["home"]["authored_prs"], 1);
+        assert_eq!(dashboard["summary"]["home"]["failing_prs"], 0);
+        assert_eq!(dashboard["summary"]["oss"]["review_requested"], 1);
+        assert!(dashboard["summary"]["oss"].get("failing_prs").is_none());
+        assert_eq!(dashboard["summary"]["oss"]["errors"]["failing_prs"], "gh CLI error: HTTP 502");
+        assert_eq!(dashboard["items"].as_array().unwrap().len(), 2);
+        assert_eq!(dashboard["items"][0]["repo"], "scottidler/gx");
+        assert_eq!(dashboard["items"][0]["account"], "home");
+        assert_eq!(dashboard["items"][0]["section"], "authored_prs");
+        assert_eq!(dashboard["items"][1]["account"], "oss");
+        assert_eq!(dashboard["items"][1]["section"], "review_requested");
+        assert_eq!(dashboard["errors"]["work"], "gh CLI error: HTTP 401");
+    }
 }
@@ -0,0 +1,163 @@
+//! Actions secrets and variables tool request types
+
+use crate::output::OutputOptions;
+use crate::secret::SecretString;
+use crate::{Error, Result};
+use rmcp::schemars;
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+/// Where a secret or variable lives: an organization, a repository or a repository environment
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ActionsScope {
+    /// Repository owner, or the organization for org-level secrets and variables
+    #[schemars(description = "Repository owner, or the organization for org-level secrets and variables")]
+    pub owner: String,
+
+    /// Repository name
+    #[schemars(description = "Repository name; omit for org-level secrets and variables")]
+    pub repo: Option<String>,
+
+    /// Environment name
+    #[schemars(description = "Deployment environment name, for environment secrets and variables (requires repo)")]
+    pub environment: Option<String>,
+}
+
+impl ActionsScope {
+    /// Collection endpoint for `kind` ("secrets" or "variables")
+    pub fn collection(&self, kind: &str) -> Result<String> {
+        match (&self.repo, &self.environment) {
+            (None, None) => Ok(format!("orgs/{}/actions/{kind}", self.owner)),
+            (Some(repo), None) => Ok(format!("repos/{}/{repo}/actions/{kind}", self.owner)),
+            (Some(repo), Some(env)) => Ok(format!("repos/{}/{repo}/environments/{env}/{kind}", self.owner)),
+            (None, Some(_)) => Err(Error::Tool("environment requires repo".to_string())),
+        }
+    }
+
+    /// True for org-level secrets and variables, which take a visibility
+    pub fn is_org(&self) -> bool {
+        self.repo.is_none()
+    }
+}
+
+/// Request parameters for list_secrets and list_variables tools
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ListActionsItemsRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Scope
+    #[serde(flatten)]
+    pub scope: ActionsScope,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+/// Request parameters for set_secret tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SetSecretRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Scope
+    #[serde(flatten)]
+    pub scope: ActionsScope,
+
+    /// Secret name
+    #[schemars(description = "Secret name (letters, digits and underscores)")]
+    pub name: String,
+
+    /// Secret value
+    #[schemars(
+        description = "Plaintext secret value. It is encrypted locally before sending and never logged or returned."
+    )]
+    pub value: SecretString,
+
+    /// Org secret visibility
+    #[schemars(description = "Org secrets only: all, private or selected (default: private)")]
+    pub visibility: Option<String>,
+
+    /// Repositories that can use a 'selected' org secret
+    #[schemars(description = "Org secrets with visibility 'selected': IDs of the repositories that can use it")]
+    pub selected_repository_ids: Option<Vec<u64>>,
+}
+
+/// Request parameters for set_variable tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SetVariableRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Scope
+    #[serde(flatten)]
+    pub scope: ActionsScope,
+
+    /// Variable name
+    #[schemars(description = "Variable name (letters, digits and underscores)")]
+    pub name: String,
+
+    /// Variable value
+    #[schemars(description = "Variable value (stored in plaintext; use set_secret for sensitive values)")]
+    pub value: String,
+
+    /// Org variable visibility
+    #[schemars(description = "Org variables only: all, private or selected (default: private)")]
+    pub visibility: Option<String>,
+
+    /// Repositories that can use a 'selected' org variable
+    #[schemars(description = "Org variables with visibility 'selected': IDs of the repositories that can use it")]
+    pub selected_repository_ids: Option<Vec<u64>>,
+}
+
+/// Request parameters for delete_secret and delete_variable tools
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct DeleteActionsItemRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Scope
+    #[serde(flatten)]
+    pub scope: ActionsScope,
+
+    /// Name to delete
+    #[schemars(description = "Name of the secret or variable to delete")]
+    pub name: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_actions_scope_collection() {
+        let scope = |json: &str| serde_json::from_str::<ListActionsItemsRequest>(json).unwrap().scope;
+
+        assert_eq!(
+            scope(r#"{"owner": "tatari-tv"}"#).collection("secrets").unwrap(),
+            "orgs/tatari-tv/actions/secrets"
+        );
+        assert_eq!(
+            scope(r#"{"owner": "scottidler", "repo": "gx"}"#).collection("variables").unwrap(),
+            "repos/scottidler/gx/actions/variables"
+        );
+        assert_eq!(
+            scope(r#"{"owner": "scottidler", "repo": "gx", "environment": "prod"}"#).collection("secrets").unwrap(),
+            "repos/scottidler/gx/environments/prod/secrets"
+        );
+        assert!(scope(r#"{"owner": "scottidler", "environment": "prod"}"#).collection("secrets").is_err());
+    }
+
+    #[test]
+    fn test_set_secret_request_debug_redacted() {
+        let json = r#"{"owner": "scottidler", "repo": "gx", "name": "API_TOKEN", "value": "hunter2"}"#;
+        let request: SetSecretRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.value.expose(), "hunter2");
+        assert!(!format!("{request:?}").contains("hunter2"));
+    }
+}
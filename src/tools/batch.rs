@@ -0,0 +1,130 @@
+//! Batch tool-call request types
+
+use crate::output::{self, OutputOptions};
+use rmcp::model::CallToolResult;
+use rmcp::schemars;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+/// Maximum number of invocations in one batch
+pub const MAX_BATCH_SIZE: usize = 50;
+
+/// One tool call within a batch
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct BatchInvocation {
+    /// Tool name
+    #[schemars(description = "Name of the tool to call, e.g. 'get_repo'")]
+    pub tool: String,
+
+    /// Tool arguments
+    #[schemars(description = "Arguments for the tool, exactly as for a direct call")]
+    #[serde(default)]
+    pub arguments: Map<String, Value>,
+}
+
+/// Request parameters for batch tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct BatchRequest {
+    /// Account for invocations that don't name one
+    #[schemars(description = "Account for invocations whose arguments don't name one (e.g., 'home', 'work')")]
+    pub account: Option<String>,
+
+    /// Tool calls to run
+    #[schemars(description = "Tool calls to run (at most 50); results are listed in the same order, with their index")]
+    pub invocations: Vec<BatchInvocation>,
+
+    /// Run in order and stop at the first failure
+    #[schemars(
+        description = "Run the calls one at a time in order and skip the rest after the first failure (default: false, which runs them all concurrently)"
+    )]
+    pub stop_on_error: Option<bool>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+/// A tool result's content as JSON: JSON text is parsed, other text kept as a string.
+///
+/// A single content item is returned as-is; several become an array.
+pub fn result_value(result: &CallToolResult) -> Value {
+    let mut items: Vec<Value> = result
+        .content
+        .iter()
+        .filter_map(|content| content.as_text())
+        .map(|text| serde_json::from_str(&text.text).unwrap_or_else(|_| Value::String(text.text.clone())))
+        .collect();
+    if items.len() == 1 { items.remove(0) } else { Value::Array(items) }
+}
+
+/// Hint appended to a batch entry whose result was cut to its share of the budget
+const BATCH_TRUNCATION_HINT: &str = "Call the tool on its own, or narrow it with 'fields' or 'jq' in its arguments.";
+
+/// Fit each entry's `result` into an equal share of the response budget (0 = unlimited).
+///
+/// Cut entries get a `truncated` note saying what was left out.
+pub fn fit_results(results: &mut [Value], budget: usize) {
+    if budget == 0 || results.is_empty() {
+        return;
+    }
+    let share = (budget / results.len()).max(1);
+    for entry in results {
+        let Some(result) = entry.get_mut("result") else {
+            continue;
+        };
+        let (fitted, omission) = output::fit_json(result.take(), share);
+        *result = fitted;
+        if let Some(omission) = omission {
+            entry["truncated"] = Value::String(omission.footer(share, BATCH_TRUNCATION_HINT));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rmcp::model::Content;
+    use serde_json::json;
+
+    #[test]
+    fn test_batch_request() {
+        let json = r#"{"invocations": [{"tool": "get_repo", "arguments": {"owner": "scottidler", "repo": "gx"}}, {"tool": "get_me"}]}"#;
+        let request: BatchRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.invocations.len(), 2);
+        assert!(request.invocations[1].arguments.is_empty());
+    }
+
+    #[test]
+    fn test_result_value() {
+        let result = CallToolResult::success(vec![Content::json(json!({"name": "gx"})).unwrap()]);
+        assert_eq!(result_value(&result), json!({"name": "gx"}));
+
+        let result = CallToolResult::success(vec![
+            Content::json(json!([1, 2])).unwrap(),
+            Content::text("Showing 2 of 9 items"),
+        ]);
+        assert_eq!(result_value(&result), json!([[1, 2], "Showing 2 of 9 items"]));
+    }
+
+    #[test]
+    fn test_fit_results() {
+        let big: Vec<Value> = (0..200).map(|i| json!({"name": format!("item-{i:04}")})).collect();
+        let mut results = vec![
+            json!({"index": 0, "tool": "list_repos", "status": "ok", "result": big.clone()}),
+            json!({"index": 1, "tool": "get_me", "status": "ok", "result": {"login": "octo"}}),
+            json!({"index": 2, "tool": "list_repos", "status": "ok", "result": big}),
+            json!({"index": 3, "tool": "get_repo", "status": "error", "error": "Not Found"}),
+        ];
+        fit_results(&mut results, 4000);
+
+        for entry in [&results[0], &results[2]] {
+            assert!(serde_json::to_string(&entry["result"]).unwrap().len() <= 1000);
+            assert!(!entry["result"].as_array().unwrap().is_empty());
+            assert!(entry["truncated"].as_str().unwrap().contains("of 200 items"));
+        }
+        assert_eq!(results[1]["result"], json!({"login": "octo"}));
+        assert!(results[1].get("truncated").is_none());
+        assert!(results[3].get("truncated").is_none());
+    }
+}
@@ -1,5 +1,6 @@
 //! Branch-related tool request types
 
+use crate::output::OutputOptions;
 use rmcp::schemars;
 use schemars::JsonSchema;
 use serde::Deserialize;
@@ -18,6 +19,10 @@ pub struct ListBranchesRequest {
     /// Repository name
     #[schemars(description = "Repository name")]
     pub repo: String,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
 }
 
 /// Request parameters for create_branch tool
@@ -42,6 +47,10 @@ pub struct CreateBranchRequest {
     /// Source branch or commit SHA to branch from (default: default branch)
     #[schemars(description = "Source branch or commit SHA to branch from (default: default branch)")]
     pub from: Option<String>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
 }
 
 /// Request parameters for delete_branch tool
@@ -83,4 +92,12 @@ mod tests {
         let request: DeleteBranchRequest = serde_json::from_str(json).unwrap();
         assert_eq!(request.branch, "feature/test");
     }
+
+    #[test]
+    fn test_list_branches_output_options() {
+        let json = r#"{"owner": "scottidler", "repo": "gx", "fields": ["name"], "jq": "map(.name)"}"#;
+        let request: ListBranchesRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.output.fields, Some(vec!["name".to_string()]));
+        assert_eq!(request.output.jq.as_deref(), Some("map(.name)"));
+    }
 }
@@ -1,8 +1,10 @@
 //! Code and content tool request types
 
+use crate::output::OutputOptions;
 use rmcp::schemars;
 use schemars::JsonSchema;
 use serde::Deserialize;
+use serde_json::{Value, json};
 
 /// Request parameters for get_file tool
 #[derive(Debug, Deserialize, JsonSchema)]
@@ -26,6 +28,10 @@ pub struct GetFileRequest {
     /// Git ref (branch, tag, or commit SHA)
     #[schemars(description = "Git ref (branch, tag, or commit SHA). Defaults to default branch.")]
     pub r#ref: Option<String>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
 }
 
 /// Request parameters for search_code tool
@@ -42,6 +48,16 @@ pub struct SearchCodeRequest {
     /// Maximum number of results
     #[schemars(description = "Maximum number of results (default: 30)")]
     pub limit: Option<u32>,
+
+    /// Search with every configured account
+    #[schemars(
+        description = "Run the search under every configured account, merging results by URL and listing which accounts can see each hit (default: false)"
+    )]
+    pub all_accounts: Option<bool>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
 }
 
 /// Request parameters for list_commits tool
@@ -74,6 +90,329 @@ pub struct ListCommitsRequest {
     /// Maximum number of commits to return
     #[schemars(description = "Maximum number of commits to return (default: 30)")]
     pub limit: Option<u32>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+/// Request parameters for compare_refs tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct CompareRefsRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Repository owner
+    #[schemars(description = "Repository owner (user or organization)")]
+    pub owner: String,
+
+    /// Repository name
+    #[schemars(description = "Repository name")]
+    pub repo: String,
+
+    /// Base ref
+    #[schemars(description = "Base branch, tag or commit SHA")]
+    pub base: String,
+
+    /// Head ref
+    #[schemars(description = "Head branch, tag or commit SHA. Use 'owner:branch' for a branch in a fork.")]
+    pub head: String,
+
+    /// Include each file's patch
+    #[schemars(description = "Include the patch for each changed file (default: false)")]
+    pub include_patch: Option<bool>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+/// Request parameters for get_file_history tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct GetFileHistoryRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Repository owner
+    #[schemars(description = "Repository owner (user or organization)")]
+    pub owner: String,
+
+    /// Repository name
+    #[schemars(description = "Repository name")]
+    pub repo: String,
+
+    /// File path within the repository
+    #[schemars(description = "File path within the repository")]
+    pub path: String,
+
+    /// Branch or commit SHA to start from
+    #[schemars(description = "Branch or commit SHA to start from. Defaults to default branch.")]
+    pub sha: Option<String>,
+
+    /// Follow renames
+    #[schemars(description = "Continue through renames to the file's previous paths (default: true)")]
+    pub follow_renames: Option<bool>,
+
+    /// Maximum number of commits to return
+    #[schemars(description = "Maximum number of commits to return (default: 30)")]
+    pub limit: Option<u32>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+/// Request parameters for blame_file tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct BlameFileRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Repository owner
+    #[schemars(description = "Repository owner (user or organization)")]
+    pub owner: String,
+
+    /// Repository name
+    #[schemars(description = "Repository name")]
+    pub repo: String,
+
+    /// File path within the repository
+    #[schemars(description = "File path within the repository")]
+    pub path: String,
+
+    /// Git ref (branch, tag, or commit SHA)
+    #[schemars(description = "Git ref (branch, tag, or commit SHA). Defaults to default branch.")]
+    pub r#ref: Option<String>,
+
+    /// First line to blame
+    #[schemars(description = "First line to blame, 1-based (default: 1)")]
+    pub start_line: Option<u64>,
+
+    /// Last line to blame
+    #[schemars(description = "Last line to blame, inclusive (default: end of file)")]
+    pub end_line: Option<u64>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+/// GraphQL query for the blame ranges of a file at a ref
+pub const BLAME_QUERY: &str = r#"query Blame($owner: String!, $repo: String!, $expression: String!, $path: String!) {
+  repository(owner: $owner, name: $repo) {
+    object(expression: $expression) {
+      ... on Commit {
+        blame(path: $path) {
+          ranges {
+            startingLine
+            endingLine
+            commit {
+              oid
+              messageHeadline
+              committedDate
+              author { name user { login } }
+            }
+          }
+        }
+      }
+    }
+  }
+}"#;
+
+/// Flatten blame ranges, keeping those overlapping `start..=end` and clamping them to it
+pub fn shape_blame(ranges: &Value, start: Option<u64>, end: Option<u64>) -> Vec<Value> {
+    let start = start.unwrap_or(1);
+    let end = end.unwrap_or(u64::MAX);
+
+    ranges
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|range| {
+            let first = range["startingLine"].as_u64()?.max(start);
+            let last = range["endingLine"].as_u64()?.min(end);
+            if first > last {
+                return None;
+            }
+            let commit = &range["commit"];
+            Some(json!({
+                "start_line": first,
+                "end_line": last,
+                "sha": commit["oid"],
+                "author": commit["author"]["user"]["login"].as_str().or(commit["author"]["name"].as_str()),
+                "date": commit["committedDate"],
+                "message": commit["messageHeadline"],
+            }))
+        })
+        .collect()
+}
+
+/// Path a file had before `commit` renamed it to `path`, from a commit details response
+pub fn renamed_from(commit: &Value, path: &str) -> Option<String> {
+    commit["files"]
+        .as_array()?
+        .iter()
+        .find(|f| f["filename"] == path && f["status"] == "renamed")
+        .and_then(|f| f["previous_filename"].as_str())
+        .map(String::from)
+}
+
+/// Request parameters for get_commit tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct GetCommitRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Repository owner
+    #[schemars(description = "Repository owner (user or organization)")]
+    pub owner: String,
+
+    /// Repository name
+    #[schemars(description = "Repository name")]
+    pub repo: String,
+
+    /// Commit SHA or ref
+    #[schemars(description = "Commit SHA, or a branch or tag name")]
+    pub sha: String,
+
+    /// Include each file's patch
+    #[schemars(description = "Include the patch for each changed file (default: false)")]
+    pub include_patch: Option<bool>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+/// Per-file stats from a commit or compare response, optionally with patches
+fn shape_files(files: &Value, include_patch: bool) -> Vec<Value> {
+    files
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .map(|f| {
+            let mut file = json!({
+                "filename": f["filename"],
+                "status": f["status"],
+                "additions": f["additions"],
+                "deletions": f["deletions"],
+            });
+            if let Some(previous) = f["previous_filename"].as_str() {
+                file["previous_filename"] = json!(previous);
+            }
+            if include_patch && let Some(patch) = f["patch"].as_str() {
+                file["patch"] = json!(patch);
+            }
+            file
+        })
+        .collect()
+}
+
+/// Combine a commit, the PRs associated with it and its combined status into one result
+pub fn shape_commit(commit: &Value, pulls: &Value, status: &Value, include_patch: bool) -> Value {
+    let person = |role: &str| {
+        json!({
+            "name": commit["commit"][role]["name"],
+            "email": commit["commit"][role]["email"],
+            "login": commit[role]["login"],
+            "date": commit["commit"][role]["date"],
+        })
+    };
+
+    let parents: Vec<&Value> = commit["parents"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .map(|p| &p["sha"])
+        .collect();
+
+    let pulls: Vec<Value> = pulls
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .map(|pr| {
+            json!({
+                "number": pr["number"],
+                "title": pr["title"],
+                "state": pr["state"],
+                "merged": !pr["merged_at"].is_null(),
+                "url": pr["html_url"],
+            })
+        })
+        .collect();
+
+    let contexts: Vec<Value> = status["statuses"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .map(|s| json!({"context": s["context"], "state": s["state"], "description": s["description"]}))
+        .collect();
+
+    json!({
+        "sha": commit["sha"],
+        "message": commit["commit"]["message"],
+        "author": person("author"),
+        "committer": person("committer"),
+        "verification": {
+            "verified": commit["commit"]["verification"]["verified"],
+            "reason": commit["commit"]["verification"]["reason"],
+        },
+        "parents": parents,
+        "stats": commit["stats"],
+        "files": shape_files(&commit["files"], include_patch),
+        "pull_requests": pulls,
+        "status": {
+            "state": status["state"],
+            "total": status["total_count"],
+            "contexts": contexts,
+        },
+        "url": commit["html_url"],
+    })
+}
+
+/// Reduce a compare API response to ahead/behind counts, commits and per-file stats
+pub fn shape_comparison(compare: &Value, include_patch: bool) -> Value {
+    let commits: Vec<Value> = compare["commits"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .map(|c| {
+            json!({
+                "sha": c["sha"],
+                "message": c["commit"]["message"].as_str().and_then(|m| m.lines().next()).unwrap_or_default(),
+                "author": c["author"]["login"].as_str().or(c["commit"]["author"]["name"].as_str()),
+                "date": c["commit"]["author"]["date"],
+            })
+        })
+        .collect();
+
+    let files = shape_files(&compare["files"], include_patch);
+
+    let sum = |key: &str| files.iter().filter_map(|f| f[key].as_u64()).sum::<u64>();
+    json!({
+        "status": compare["status"],
+        "ahead_by": compare["ahead_by"],
+        "behind_by": compare["behind_by"],
+        "merge_base": compare["merge_base_commit"]["sha"],
+        "total_commits": compare["total_commits"],
+        "stats": {
+            "files": files.len(),
+            "additions": sum("additions"),
+            "deletions": sum("deletions"),
+        },
+        "commits": commits,
+        "files": files,
+    })
 }
 
 #[cfg(test)]
@@ -101,4 +440,102 @@ mod tests {
         assert_eq!(request.sha, Some("main".to_string()));
         assert_eq!(request.limit, Some(10));
     }
+
+    #[test]
+    fn test_shape_comparison() {
+        let compare = json!({
+            "status": "diverged",
+            "ahead_by": 1,
+            "behind_by": 2,
+            "total_commits": 1,
+            "merge_base_commit": {"sha": "base123"},
+            "commits": [{
+                "sha": "abc123",
+                "author": {"login": "scottidler"},
+                "commit": {"message": "Fix bug\n\nDetails", "author": {"name": "Scott", "date": "2025-01-01T00:00:00Z"}}
+            }],
+            "files": [
+                {"filename": "src/new.rs", "previous_filename": "src/old.rs", "status": "renamed", "additions": 3, "deletions": 1, "patch": "@@ -1 +1 @@"},
+                {"filename": "README.md", "status": "modified", "additions": 2, "deletions": 0, "patch": "@@ -1 +1,2 @@"}
+            ]
+        });
+
+        let shaped = shape_comparison(&compare, false);
+        assert_eq!(shaped["ahead_by"], 1);
+        assert_eq!(shaped["behind_by"], 2);
+        assert_eq!(shaped["merge_base"], "base123");
+        assert_eq!(shaped["commits"][0]["message"], "Fix bug");
+        assert_eq!(shaped["commits"][0]["author"], "scottidler");
+        assert_eq!(shaped["stats"], json!({"files": 2, "additions": 5, "deletions": 1}));
+        assert_eq!(shaped["files"][0]["previous_filename"], "src/old.rs");
+        assert!(shaped["files"][0].get("patch").is_none());
+
+        let shaped = shape_comparison(&compare, true);
+        assert_eq!(shaped["files"][1]["patch"], "@@ -1 +1,2 @@");
+    }
+
+    #[test]
+    fn test_shape_blame() {
+        let commit = |oid: &str| json!({"oid": oid, "messageHeadline": "msg", "committedDate": "2025-01-01T00:00:00Z", "author": {"name": "Scott", "user": {"login": "scottidler"}}});
+        let ranges = json!([
+            {"startingLine": 1, "endingLine": 4, "commit": commit("aaa")},
+            {"startingLine": 5, "endingLine": 9, "commit": commit("bbb")},
+            {"startingLine": 10, "endingLine": 12, "commit": commit("ccc")}
+        ]);
+
+        assert_eq!(shape_blame(&ranges, None, None).len(), 3);
+
+        let blame = shape_blame(&ranges, Some(3), Some(6));
+        assert_eq!(blame.len(), 2);
+        assert_eq!((blame[0]["start_line"].as_u64(), blame[0]["end_line"].as_u64()), (Some(3), Some(4)));
+        assert_eq!((blame[1]["start_line"].as_u64(), blame[1]["end_line"].as_u64()), (Some(5), Some(6)));
+        assert_eq!(blame[1]["sha"], "bbb");
+        assert_eq!(blame[1]["author"], "scottidler");
+    }
+
+    #[test]
+    fn test_renamed_from() {
+        let commit = json!({
+            "files": [
+                {"filename": "src/main.rs", "status": "modified"},
+                {"filename": "src/gh.rs", "status": "renamed", "previous_filename": "src/client.rs"}
+            ]
+        });
+        assert_eq!(renamed_from(&commit, "src/gh.rs"), Some("src/client.rs".to_string()));
+        assert_eq!(renamed_from(&commit, "src/main.rs"), None);
+    }
+
+    #[test]
+    fn test_shape_commit() {
+        let commit = json!({
+            "sha": "abc123",
+            "html_url": "https://github.com/o/r/commit/abc123",
+            "author": {"login": "scottidler"},
+            "committer": {"login": "web-flow"},
+            "commit": {
+                "message": "Fix bug\n\nDetails",
+                "author": {"name": "Scott", "email": "s@example.com", "date": "2025-01-01T00:00:00Z"},
+                "committer": {"name": "GitHub", "email": "noreply@github.com", "date": "2025-01-01T00:00:00Z"},
+                "verification": {"verified": true, "reason": "valid", "signature": "-----BEGIN PGP SIGNATURE-----"}
+            },
+            "parents": [{"sha": "p1", "url": "..."}],
+            "stats": {"total": 3, "additions": 2, "deletions": 1},
+            "files": [{"filename": "src/lib.rs", "status": "modified", "additions": 2, "deletions": 1, "patch": "@@"}]
+        });
+        let pulls = json!([{"number": 7, "title": "Fix", "state": "closed", "merged_at": "2025-01-02T00:00:00Z", "html_url": "u"}]);
+        let status = json!({"state": "success", "total_count": 1, "statuses": [{"context": "ci", "state": "success", "description": "ok"}]});
+
+        let shaped = shape_commit(&commit, &pulls, &status, false);
+        assert_eq!(shaped["author"]["login"], "scottidler");
+        assert_eq!(shaped["committer"]["name"], "GitHub");
+        assert_eq!(shaped["verification"], json!({"verified": true, "reason": "valid"}));
+        assert_eq!(shaped["parents"], json!(["p1"]));
+        assert!(shaped["files"][0].get("patch").is_none());
+        assert_eq!(shaped["pull_requests"][0]["merged"], true);
+        assert_eq!(shaped["status"]["state"], "success");
+        assert_eq!(shaped["status"]["contexts"][0]["context"], "ci");
+
+        let shaped = shape_commit(&commit, &pulls, &status, true);
+        assert_eq!(shaped["files"][0]["patch"], "@@");
+    }
 }
@@ -0,0 +1,458 @@
+//! Deployment environment and deployment tool request types
+
+use crate::output::OutputOptions;
+use crate::{Error, Result};
+use rmcp::schemars;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+/// Request parameters for list_environments tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ListEnvironmentsRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Repository owner
+    #[schemars(description = "Repository owner (user or organization)")]
+    pub owner: String,
+
+    /// Repository name
+    #[schemars(description = "Repository name")]
+    pub repo: String,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+/// A required reviewer for an environment, by login or team slug
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EnvironmentReviewer {
+    /// A user login
+    User(String),
+    /// A team slug in the repository's organization
+    Team(String),
+}
+
+/// Which branches can deploy to an environment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BranchPolicy {
+    /// Any branch
+    All,
+    /// Branches with branch protection
+    Protected,
+    /// Branches matching `branch_patterns`
+    Custom,
+}
+
+/// Request parameters for set_environment tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SetEnvironmentRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Repository owner
+    #[schemars(description = "Repository owner (user or organization)")]
+    pub owner: String,
+
+    /// Repository name
+    #[schemars(description = "Repository name")]
+    pub repo: String,
+
+    /// Environment name
+    #[schemars(description = "Environment name; created if it doesn't exist")]
+    pub environment: String,
+
+    /// Wait timer in minutes
+    #[schemars(description = "Minutes to wait before deployments proceed (0-43200)")]
+    pub wait_timer: Option<u32>,
+
+    /// Required reviewers
+    #[schemars(
+        description = "Required reviewers (up to 6), e.g. [{'user': 'scottidler'}, {'team': 'platform'}]. An empty list removes them."
+    )]
+    pub reviewers: Option<Vec<EnvironmentReviewer>>,
+
+    /// Prevent self-review
+    #[schemars(description = "Prevent the user who triggered a deployment from approving it")]
+    pub prevent_self_review: Option<bool>,
+
+    /// Which branches can deploy
+    #[schemars(description = "Which branches can deploy: all, protected or custom (with branch_patterns)")]
+    pub branch_policy: Option<BranchPolicy>,
+
+    /// Branch name patterns for a custom policy
+    #[schemars(description = "Branch name patterns (e.g. 'main', 'release/*') allowed with branch_policy 'custom'")]
+    pub branch_patterns: Option<Vec<String>>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+impl SetEnvironmentRequest {
+    /// The branch policy to apply; `branch_patterns` alone implies a custom policy
+    pub fn branch_policy(&self) -> Result<Option<BranchPolicy>> {
+        match (self.branch_policy, &self.branch_patterns) {
+            (None | Some(BranchPolicy::Custom), Some(_)) => Ok(Some(BranchPolicy::Custom)),
+            (Some(_), Some(_)) => Err(Error::Tool("branch_patterns requires branch_policy 'custom'".to_string())),
+            (policy, None) => Ok(policy),
+        }
+    }
+
+    /// Request body for `PUT repos/{owner}/{repo}/environments/{name}`, given resolved reviewer IDs
+    pub fn to_body(&self, reviewers: Option<&[(&str, u64)]>) -> Result<Value> {
+        let mut body = json!({});
+        if let Some(wait_timer) = self.wait_timer {
+            body["wait_timer"] = json!(wait_timer);
+        }
+        if let Some(prevent_self_review) = self.prevent_self_review {
+            body["prevent_self_review"] = json!(prevent_self_review);
+        }
+        if let Some(reviewers) = reviewers {
+            body["reviewers"] = reviewers
+                .iter()
+                .map(|(kind, id)| json!({ "type": kind, "id": id }))
+                .collect();
+        }
+        if let Some(policy) = self.branch_policy()? {
+            body["deployment_branch_policy"] = match policy {
+                BranchPolicy::All => Value::Null,
+                BranchPolicy::Protected => json!({ "protected_branches": true, "custom_branch_policies": false }),
+                BranchPolicy::Custom => json!({ "protected_branches": false, "custom_branch_policies": true }),
+            };
+        }
+        Ok(body)
+    }
+}
+
+/// Request parameters for delete_environment tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct DeleteEnvironmentRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Repository owner
+    #[schemars(description = "Repository owner (user or organization)")]
+    pub owner: String,
+
+    /// Repository name
+    #[schemars(description = "Repository name")]
+    pub repo: String,
+
+    /// Environment name
+    #[schemars(description = "Environment name to delete")]
+    pub environment: String,
+}
+
+/// Request parameters for list_deployments tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ListDeploymentsRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Repository owner
+    #[schemars(description = "Repository owner (user or organization)")]
+    pub owner: String,
+
+    /// Repository name
+    #[schemars(description = "Repository name")]
+    pub repo: String,
+
+    /// Filter by environment
+    #[schemars(description = "Filter by environment name")]
+    pub environment: Option<String>,
+
+    /// Filter by ref
+    #[schemars(description = "Filter by ref (branch, tag or SHA) the deployment was created for")]
+    pub r#ref: Option<String>,
+
+    /// Maximum number of deployments to return
+    #[schemars(description = "Maximum number of deployments to return (default: 30)")]
+    pub limit: Option<u32>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+/// Request parameters for create_deployment tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct CreateDeploymentRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Repository owner
+    #[schemars(description = "Repository owner (user or organization)")]
+    pub owner: String,
+
+    /// Repository name
+    #[schemars(description = "Repository name")]
+    pub repo: String,
+
+    /// Ref to deploy
+    #[schemars(description = "Branch, tag or SHA to deploy")]
+    pub r#ref: String,
+
+    /// Target environment
+    #[schemars(description = "Environment to deploy to (default: production)")]
+    pub environment: Option<String>,
+
+    /// Description
+    #[schemars(description = "Short description of the deployment")]
+    pub description: Option<String>,
+
+    /// Merge the default branch into the ref first
+    #[schemars(description = "Merge the default branch into the ref if it's behind (default: false)")]
+    pub auto_merge: Option<bool>,
+
+    /// Status contexts that must pass
+    #[schemars(description = "Status check contexts that must pass first; an empty list skips the check (default: all)")]
+    pub required_contexts: Option<Vec<String>>,
+
+    /// Extra data for deployment consumers
+    #[schemars(description = "JSON payload with extra information for deployment consumers")]
+    pub payload: Option<Value>,
+
+    /// Whether this is a production environment
+    #[schemars(description = "Whether the environment is one with which end users interact")]
+    pub production_environment: Option<bool>,
+
+    /// Whether the environment is transient
+    #[schemars(description = "Whether the environment will no longer exist at some point (e.g. a review app)")]
+    pub transient_environment: Option<bool>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+impl CreateDeploymentRequest {
+    /// Request body for `POST repos/{owner}/{repo}/deployments`
+    pub fn to_body(&self) -> Value {
+        let mut body = json!({
+            "ref": self.r#ref,
+            "auto_merge": self.auto_merge.unwrap_or(false),
+        });
+        if let Some(ref environment) = self.environment {
+            body["environment"] = json!(environment);
+        }
+        if let Some(ref description) = self.description {
+            body["description"] = json!(description);
+        }
+        if let Some(ref contexts) = self.required_contexts {
+            body["required_contexts"] = json!(contexts);
+        }
+        if let Some(ref payload) = self.payload {
+            body["payload"] = payload.clone();
+        }
+        if let Some(production) = self.production_environment {
+            body["production_environment"] = json!(production);
+        }
+        if let Some(transient) = self.transient_environment {
+            body["transient_environment"] = json!(transient);
+        }
+        body
+    }
+}
+
+/// Request parameters for list_deployment_statuses tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ListDeploymentStatusesRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Repository owner
+    #[schemars(description = "Repository owner (user or organization)")]
+    pub owner: String,
+
+    /// Repository name
+    #[schemars(description = "Repository name")]
+    pub repo: String,
+
+    /// Deployment ID
+    #[schemars(description = "Deployment ID")]
+    pub deployment_id: u64,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+/// Request parameters for create_deployment_status tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct CreateDeploymentStatusRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Repository owner
+    #[schemars(description = "Repository owner (user or organization)")]
+    pub owner: String,
+
+    /// Repository name
+    #[schemars(description = "Repository name")]
+    pub repo: String,
+
+    /// Deployment ID
+    #[schemars(description = "Deployment ID")]
+    pub deployment_id: u64,
+
+    /// Status state
+    #[schemars(description = "State: error, failure, inactive, in_progress, queued, pending or success")]
+    pub state: String,
+
+    /// Description
+    #[schemars(description = "Short description of the status")]
+    pub description: Option<String>,
+
+    /// URL of the deployed environment
+    #[schemars(description = "URL for accessing the deployed environment")]
+    pub environment_url: Option<String>,
+
+    /// URL of the deployment log
+    #[schemars(description = "URL of the deployment output")]
+    pub log_url: Option<String>,
+
+    /// Mark earlier deployments inactive
+    #[schemars(description = "Mark earlier non-transient, non-production deployments to the environment inactive on success (default: true)")]
+    pub auto_inactive: Option<bool>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+/// Request parameters for approve_pending_deployment tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ApprovePendingDeploymentRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Repository owner
+    #[schemars(description = "Repository owner (user or organization)")]
+    pub owner: String,
+
+    /// Repository name
+    #[schemars(description = "Repository name")]
+    pub repo: String,
+
+    /// Workflow run ID
+    #[schemars(description = "ID of the workflow run waiting for review")]
+    pub run_id: u64,
+
+    /// Environments to review
+    #[schemars(description = "Environment names to review (default: every pending environment you can approve)")]
+    pub environments: Option<Vec<String>>,
+
+    /// Reject instead of approving
+    #[schemars(description = "Reject the deployments instead of approving them (default: false)")]
+    pub reject: Option<bool>,
+
+    /// Review comment
+    #[schemars(description = "Review comment")]
+    pub comment: Option<String>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+/// Environment IDs to review from a run's pending deployments.
+///
+/// With `names`, every named environment must be pending and approvable; otherwise all
+/// environments the current user can approve are selected.
+pub fn select_pending_environments(pending: &Value, names: Option<&[String]>) -> Result<Vec<u64>> {
+    let pending = pending.as_array().map(Vec::as_slice).unwrap_or_default();
+    let approvable = |p: &&Value| p["current_user_can_approve"].as_bool().unwrap_or(false);
+
+    let Some(names) = names else {
+        let ids: Vec<u64> = pending
+            .iter()
+            .filter(approvable)
+            .filter_map(|p| p["environment"]["id"].as_u64())
+            .collect();
+        if ids.is_empty() {
+            return Err(Error::Tool("no pending deployments you can approve".to_string()));
+        }
+        return Ok(ids);
+    };
+
+    names
+        .iter()
+        .map(|name| {
+            let deployment = pending
+                .iter()
+                .find(|p| p["environment"]["name"] == name.as_str())
+                .ok_or_else(|| Error::Tool(format!("no pending deployment for environment '{name}'")))?;
+            if !approvable(&deployment) {
+                return Err(Error::Tool(format!("you can't approve deployments to '{name}'")));
+            }
+            deployment["environment"]["id"]
+                .as_u64()
+                .ok_or_else(|| Error::Tool(format!("environment '{name}' has no id")))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_environment_body() {
+        let json = r#"{
+            "owner": "tatari-tv", "repo": "svc", "environment": "production",
+            "wait_timer": 10, "reviewers": [{"user": "scottidler"}, {"team": "platform"}],
+            "branch_policy": "protected"
+        }"#;
+        let request: SetEnvironmentRequest = serde_json::from_str(json).unwrap();
+        assert!(matches!(request.reviewers.as_deref(), Some([EnvironmentReviewer::User(_), EnvironmentReviewer::Team(_)])));
+
+        let body = request.to_body(Some(&[("User", 1), ("Team", 2)])).unwrap();
+        assert_eq!(
+            body,
+            json!({
+                "wait_timer": 10,
+                "reviewers": [{"type": "User", "id": 1}, {"type": "Team", "id": 2}],
+                "deployment_branch_policy": {"protected_branches": true, "custom_branch_policies": false}
+            })
+        );
+
+        let json = r#"{"owner": "o", "repo": "r", "environment": "prod", "branch_patterns": ["release/*"]}"#;
+        let request: SetEnvironmentRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.branch_policy().unwrap(), Some(BranchPolicy::Custom));
+        assert!(request.to_body(None).unwrap().get("reviewers").is_none());
+
+        let json = r#"{"owner": "o", "repo": "r", "environment": "prod", "branch_policy": "all", "branch_patterns": ["main"]}"#;
+        let request: SetEnvironmentRequest = serde_json::from_str(json).unwrap();
+        assert!(request.to_body(None).is_err());
+    }
+
+    #[test]
+    fn test_select_pending_environments() {
+        let pending = json!([
+            {"environment": {"id": 10, "name": "staging"}, "current_user_can_approve": true},
+            {"environment": {"id": 20, "name": "production"}, "current_user_can_approve": false}
+        ]);
+
+        assert_eq!(select_pending_environments(&pending, None).unwrap(), vec![10]);
+        assert_eq!(
+            select_pending_environments(&pending, Some(&["staging".to_string()])).unwrap(),
+            vec![10]
+        );
+        assert!(select_pending_environments(&pending, Some(&["production".to_string()])).is_err());
+        assert!(select_pending_environments(&pending, Some(&["qa".to_string()])).is_err());
+        assert!(select_pending_environments(&json!([]), None).is_err());
+    }
+}
@@ -0,0 +1,116 @@
+//! Fork tool request types
+
+use crate::output::OutputOptions;
+use rmcp::schemars;
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+/// Request parameters for fork_repo tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ForkRepoRequest {
+    /// The account that can read the source repository
+    #[schemars(
+        description = "The account that can read the source repository (e.g., 'home', 'work'). Uses default if not specified."
+    )]
+    pub account: Option<String>,
+
+    /// Source repository owner
+    #[schemars(description = "Owner of the repository to fork")]
+    pub owner: String,
+
+    /// Source repository name
+    #[schemars(description = "Name of the repository to fork")]
+    pub repo: String,
+
+    /// Account that will own the fork
+    #[schemars(description = "Account that creates and owns the fork (default: same as 'account')")]
+    pub destination_account: Option<String>,
+
+    /// Organization to fork into
+    #[schemars(description = "Organization to create the fork in, instead of the destination account's user")]
+    pub org: Option<String>,
+
+    /// Name of the fork
+    #[schemars(description = "Name for the fork (default: same as the source)")]
+    pub name: Option<String>,
+
+    /// Copy only the default branch
+    #[schemars(description = "Fork only the default branch (default: false)")]
+    pub default_branch_only: Option<bool>,
+
+    /// Grant the destination read access if it can't see the source
+    #[schemars(
+        description = "If the destination account can't access the source (e.g. a private repo), invite it as a read collaborator using 'account' and accept the invitation with the destination account before forking (default: false)"
+    )]
+    pub grant_access: Option<bool>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+/// Request parameters for list_forks tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ListForksRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Repository owner
+    #[schemars(description = "Repository owner (user or organization)")]
+    pub owner: String,
+
+    /// Repository name
+    #[schemars(description = "Repository name")]
+    pub repo: String,
+
+    /// Sort order
+    #[schemars(description = "Sort order: newest, oldest, stargazers, watchers (default: newest)")]
+    pub sort: Option<String>,
+
+    /// Maximum number of forks to return
+    #[schemars(description = "Maximum number of forks to return (default: 30)")]
+    pub limit: Option<u32>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+/// Request parameters for sync_fork tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SyncForkRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Fork owner
+    #[schemars(description = "Owner of the fork")]
+    pub owner: String,
+
+    /// Fork name
+    #[schemars(description = "Name of the fork")]
+    pub repo: String,
+
+    /// Branch to sync
+    #[schemars(description = "Branch to update from the upstream repository (default: the fork's default branch)")]
+    pub branch: Option<String>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fork_repo_request() {
+        let json = r#"{"account": "work", "owner": "tatari-tv", "repo": "svc", "destination_account": "home", "grant_access": true}"#;
+        let request: ForkRepoRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.destination_account, Some("home".to_string()));
+        assert_eq!(request.grant_access, Some(true));
+        assert!(request.org.is_none());
+    }
+}
@@ -0,0 +1,452 @@
+//! Label and milestone tool request types
+
+use crate::output::OutputOptions;
+use rmcp::schemars;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+/// Request parameters for list_labels tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ListLabelsRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Repository owner
+    #[schemars(description = "Repository owner (user or organization)")]
+    pub owner: String,
+
+    /// Repository name
+    #[schemars(description = "Repository name")]
+    pub repo: String,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+/// Request parameters for create_label tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct CreateLabelRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Repository owner
+    #[schemars(description = "Repository owner (user or organization)")]
+    pub owner: String,
+
+    /// Repository name
+    #[schemars(description = "Repository name")]
+    pub repo: String,
+
+    /// Label name
+    #[schemars(description = "Label name")]
+    pub name: String,
+
+    /// Label color
+    #[schemars(description = "Hex color without the leading '#' (e.g., 'd73a4a')")]
+    pub color: String,
+
+    /// Label description
+    #[schemars(description = "Short description of the label")]
+    pub description: Option<String>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+/// Request parameters for update_label tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct UpdateLabelRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Repository owner
+    #[schemars(description = "Repository owner (user or organization)")]
+    pub owner: String,
+
+    /// Repository name
+    #[schemars(description = "Repository name")]
+    pub repo: String,
+
+    /// Current label name
+    #[schemars(description = "Current label name")]
+    pub name: String,
+
+    /// New label name
+    #[schemars(description = "New label name")]
+    pub new_name: Option<String>,
+
+    /// New label color
+    #[schemars(description = "New hex color without the leading '#'")]
+    pub color: Option<String>,
+
+    /// New label description
+    #[schemars(description = "New description")]
+    pub description: Option<String>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+/// Request parameters for delete_label tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct DeleteLabelRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Repository owner
+    #[schemars(description = "Repository owner (user or organization)")]
+    pub owner: String,
+
+    /// Repository name
+    #[schemars(description = "Repository name")]
+    pub repo: String,
+
+    /// Label name to delete
+    #[schemars(description = "Label name to delete")]
+    pub name: String,
+}
+
+/// Request parameters for add_labels tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct AddLabelsRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Repository owner
+    #[schemars(description = "Repository owner (user or organization)")]
+    pub owner: String,
+
+    /// Repository name
+    #[schemars(description = "Repository name")]
+    pub repo: String,
+
+    /// Issue or pull request number
+    #[schemars(description = "Issue or pull request number")]
+    pub number: u64,
+
+    /// Labels to add
+    #[schemars(description = "Label names to add")]
+    pub labels: Vec<String>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+/// Request parameters for remove_label tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct RemoveLabelRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Repository owner
+    #[schemars(description = "Repository owner (user or organization)")]
+    pub owner: String,
+
+    /// Repository name
+    #[schemars(description = "Repository name")]
+    pub repo: String,
+
+    /// Issue or pull request number
+    #[schemars(description = "Issue or pull request number")]
+    pub number: u64,
+
+    /// Label to remove
+    #[schemars(description = "Label name to remove")]
+    pub label: String,
+}
+
+/// Request parameters for list_milestones tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ListMilestonesRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Repository owner
+    #[schemars(description = "Repository owner (user or organization)")]
+    pub owner: String,
+
+    /// Repository name
+    #[schemars(description = "Repository name")]
+    pub repo: String,
+
+    /// Filter by state
+    #[schemars(description = "Filter by state: open, closed, all (default: open)")]
+    pub state: Option<String>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+/// Request parameters for create_milestone tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct CreateMilestoneRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Repository owner
+    #[schemars(description = "Repository owner (user or organization)")]
+    pub owner: String,
+
+    /// Repository name
+    #[schemars(description = "Repository name")]
+    pub repo: String,
+
+    /// Milestone title
+    #[schemars(description = "Milestone title")]
+    pub title: String,
+
+    /// Milestone description
+    #[schemars(description = "Milestone description")]
+    pub description: Option<String>,
+
+    /// Due date
+    #[schemars(description = "Due date as an ISO 8601 timestamp (e.g., '2025-06-30T00:00:00Z')")]
+    pub due_on: Option<String>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+/// Request parameters for update_milestone tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct UpdateMilestoneRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Repository owner
+    #[schemars(description = "Repository owner (user or organization)")]
+    pub owner: String,
+
+    /// Repository name
+    #[schemars(description = "Repository name")]
+    pub repo: String,
+
+    /// Milestone number
+    #[schemars(description = "Milestone number")]
+    pub number: u64,
+
+    /// New title
+    #[schemars(description = "New title")]
+    pub title: Option<String>,
+
+    /// New description
+    #[schemars(description = "New description")]
+    pub description: Option<String>,
+
+    /// New due date
+    #[schemars(description = "New due date as an ISO 8601 timestamp")]
+    pub due_on: Option<String>,
+
+    /// New state
+    #[schemars(description = "New state: open or closed")]
+    pub state: Option<String>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+/// Desired state of a single label
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+pub struct LabelSpec {
+    /// Label name (matched case-insensitively)
+    #[schemars(description = "Label name (matched case-insensitively)")]
+    pub name: String,
+
+    /// Label color
+    #[schemars(description = "Hex color, with or without the leading '#'")]
+    pub color: String,
+
+    /// Label description
+    #[schemars(description = "Label description; omit to leave an existing description unchanged")]
+    pub description: Option<String>,
+}
+
+/// Request parameters for sync_labels tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SyncLabelsRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Repository owner
+    #[schemars(description = "Repository owner (user or organization)")]
+    pub owner: String,
+
+    /// Repository name
+    #[schemars(description = "Repository name")]
+    pub repo: String,
+
+    /// Desired label set
+    #[schemars(description = "Desired label set")]
+    pub labels: Vec<LabelSpec>,
+
+    /// Delete labels not in the spec
+    #[schemars(description = "Delete existing labels that are not in the spec (default: false)")]
+    pub delete_missing: Option<bool>,
+
+    /// Report the changes without applying them
+    #[schemars(description = "Only report the changes that would be made (default: false)")]
+    pub dry_run: Option<bool>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+/// An existing label whose color or description differs from the spec
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LabelUpdate {
+    /// Current name of the label
+    pub name: String,
+
+    /// Desired state
+    pub to: LabelSpec,
+
+    /// Fields that differ (name, color, description)
+    pub changes: Vec<&'static str>,
+}
+
+/// Changes needed to make a repository's labels match a spec
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub struct LabelSyncPlan {
+    pub create: Vec<LabelSpec>,
+    pub update: Vec<LabelUpdate>,
+    pub delete: Vec<String>,
+    pub unchanged: Vec<String>,
+}
+
+impl LabelSyncPlan {
+    /// True if the labels already match the spec
+    pub fn is_empty(&self) -> bool {
+        self.create.is_empty() && self.update.is_empty() && self.delete.is_empty()
+    }
+}
+
+fn normalize_color(color: &str) -> String {
+    color.trim_start_matches('#').to_lowercase()
+}
+
+/// Compare existing labels (as returned by the labels API) with the desired spec
+pub fn plan_label_sync(existing: &[Value], spec: &[LabelSpec], delete_missing: bool) -> LabelSyncPlan {
+    let mut plan = LabelSyncPlan::default();
+
+    for want in spec {
+        let want = LabelSpec {
+            color: normalize_color(&want.color),
+            ..want.clone()
+        };
+        let current = existing
+            .iter()
+            .find(|label| label["name"].as_str().is_some_and(|n| n.eq_ignore_ascii_case(&want.name)));
+
+        let Some(current) = current else {
+            plan.create.push(want);
+            continue;
+        };
+
+        let name = current["name"].as_str().unwrap_or_default().to_string();
+        let mut changes = Vec::new();
+        if name != want.name {
+            changes.push("name");
+        }
+        if normalize_color(current["color"].as_str().unwrap_or_default()) != want.color {
+            changes.push("color");
+        }
+        if let Some(ref description) = want.description
+            && current["description"].as_str().unwrap_or_default() != description
+        {
+            changes.push("description");
+        }
+
+        if changes.is_empty() {
+            plan.unchanged.push(name);
+        } else {
+            plan.update.push(LabelUpdate { name, to: want, changes });
+        }
+    }
+
+    if delete_missing {
+        plan.delete = existing
+            .iter()
+            .filter_map(|label| label["name"].as_str())
+            .filter(|name| !spec.iter().any(|want| want.name.eq_ignore_ascii_case(name)))
+            .map(String::from)
+            .collect();
+    }
+
+    plan
+}
+
+/// Request body for creating or updating a label from a spec
+pub fn label_body(spec: &LabelSpec) -> Value {
+    let mut body = json!({ "color": normalize_color(&spec.color) });
+    if let Some(ref description) = spec.description {
+        body["description"] = json!(description);
+    }
+    body
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(name: &str, color: &str, description: Option<&str>) -> LabelSpec {
+        LabelSpec {
+            name: name.to_string(),
+            color: color.to_string(),
+            description: description.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_sync_labels_request() {
+        let json = r##"{"owner": "scottidler", "repo": "gx", "labels": [{"name": "bug", "color": "#d73a4a"}]}"##;
+        let request: SyncLabelsRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.labels[0].name, "bug");
+        assert!(request.labels[0].description.is_none());
+        assert!(request.delete_missing.is_none());
+    }
+
+    #[test]
+    fn test_plan_label_sync() {
+        let existing = vec![
+            json!({"name": "bug", "color": "d73a4a", "description": "Something isn't working"}),
+            json!({"name": "Enhancement", "color": "a2eeef", "description": "New feature"}),
+            json!({"name": "wontfix", "color": "ffffff", "description": ""}),
+        ];
+        let desired = vec![
+            spec("bug", "#D73A4A", None),
+            spec("enhancement", "a2eeef", Some("New feature or request")),
+            spec("needs-triage", "fbca04", None),
+        ];
+
+        let plan = plan_label_sync(&existing, &desired, false);
+        assert_eq!(plan.unchanged, vec!["bug"]);
+        assert_eq!(plan.create, vec![spec("needs-triage", "fbca04", None)]);
+        assert_eq!(plan.update.len(), 1);
+        assert_eq!(plan.update[0].name, "Enhancement");
+        assert_eq!(plan.update[0].changes, vec!["name", "description"]);
+        assert!(plan.delete.is_empty());
+
+        let plan = plan_label_sync(&existing, &desired, true);
+        assert_eq!(plan.delete, vec!["wontfix"]);
+        assert!(!plan.is_empty());
+    }
+}
@@ -1,12 +1,22 @@
 //! Tool implementations for GitHub MCP server
 
 pub mod account;
+pub mod actions;
+pub mod batch;
 pub mod branches;
 pub mod code;
+pub mod environments;
+pub mod forks;
+pub mod labels;
+pub mod notifications;
+pub mod policy;
 pub mod protection;
 pub mod prs;
 pub mod releases;
 pub mod repos;
+pub mod rulesets;
+pub mod search;
 pub mod tags;
 pub mod teams;
+pub mod webhooks;
 pub mod workflows;
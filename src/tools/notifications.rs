@@ -0,0 +1,213 @@
+//! Notification tool request types
+
+use crate::gh::encode_segment;
+use crate::output::OutputOptions;
+use rmcp::schemars;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::{Map, Value, json};
+
+/// Request parameters for list_notifications tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ListNotificationsRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Merge the notifications of every account
+    #[schemars(
+        description = "List notifications of every configured account, newest first, each tagged with its account (default: false)"
+    )]
+    pub all_accounts: Option<bool>,
+
+    /// Only threads the user participates in
+    #[schemars(description = "Only notifications for threads you participate in or are @mentioned in (default: false)")]
+    pub participating: Option<bool>,
+
+    /// Include read notifications
+    #[schemars(description = "Include notifications already marked as read (default: false)")]
+    pub all: Option<bool>,
+
+    /// Limit to a repository
+    #[schemars(description = "Only notifications for this repository, as 'owner/repo'")]
+    pub repo: Option<String>,
+
+    /// Limit to a reason
+    #[schemars(
+        description = "Only notifications with this reason: assign, author, comment, mention, review_requested, state_change, subscribed, team_mention, ci_activity, ... GitHub can't filter by reason, so notifications are fetched page by page and filtered here until 'limit' match."
+    )]
+    pub reason: Option<String>,
+
+    /// Only notifications updated after this time
+    #[schemars(description = "Only notifications updated after this ISO 8601 timestamp")]
+    pub since: Option<String>,
+
+    /// Maximum number of notifications per account
+    #[schemars(description = "Maximum number of notifications per account (default: 50)")]
+    pub limit: Option<u32>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+impl ListNotificationsRequest {
+    /// Notifications endpoint with filter query parameters; paging parameters are added per request
+    pub fn endpoint(&self) -> String {
+        let base = match self.repo {
+            Some(ref repo) => format!("repos/{repo}/notifications"),
+            None => "notifications".to_string(),
+        };
+        let mut query = Vec::new();
+        if self.participating.unwrap_or(false) {
+            query.push("participating=true".to_string());
+        }
+        if self.all.unwrap_or(false) {
+            query.push("all=true".to_string());
+        }
+        if let Some(ref since) = self.since {
+            query.push(format!("since={}", encode_segment(since)));
+        }
+        if query.is_empty() { base } else { format!("{base}?{}", query.join("&")) }
+    }
+}
+
+/// Request parameters for mark_notification_read tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct MarkNotificationReadRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Notification thread ID
+    #[schemars(description = "Notification thread ID (the 'id' from list_notifications)")]
+    pub thread_id: String,
+}
+
+/// Request parameters for mark_all_read tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct MarkAllReadRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Limit to a repository
+    #[schemars(description = "Only mark this repository's notifications, as 'owner/repo' (default: all repositories)")]
+    pub repo: Option<String>,
+
+    /// Mark notifications up to this time
+    #[schemars(description = "Only mark notifications last updated before this ISO 8601 timestamp (default: now)")]
+    pub last_read_at: Option<String>,
+}
+
+/// Request parameters for subscribe_thread and unsubscribe_thread tools
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ThreadSubscriptionRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Notification thread ID
+    #[schemars(description = "Notification thread ID (the 'id' from list_notifications)")]
+    pub thread_id: String,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+/// Compact notifications, keeping those with the given reason, and tag them with an account
+pub fn shape_notifications(notifications: &Value, reason: Option<&str>, account: Option<&str>) -> Vec<Value> {
+    notifications
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .filter(|n| reason.is_none_or(|reason| n["reason"] == reason))
+        .map(|n| {
+            let mut shaped = json!({
+                "id": n["id"],
+                "reason": n["reason"],
+                "unread": n["unread"],
+                "updated_at": n["updated_at"],
+                "repo": n["repository"]["full_name"],
+                "type": n["subject"]["type"],
+                "title": n["subject"]["title"],
+                "url": n["subject"]["url"],
+            });
+            if let Some(account) = account {
+                shaped["account"] = json!(account);
+            }
+            shaped
+        })
+        .collect()
+}
+
+/// Merge per-account notifications, newest first, with per-account failures under `errors`
+pub fn merge_notifications(per_account: Vec<(String, crate::Result<Vec<Value>>)>) -> Value {
+    let mut notifications = Vec::new();
+    let mut errors = Map::new();
+    for (account, result) in per_account {
+        match result {
+            Ok(items) => notifications.extend(items),
+            Err(e) => {
+                errors.insert(account, json!(e.to_string()));
+            }
+        }
+    }
+    notifications.sort_by(|a, b| b["updated_at"].as_str().cmp(&a["updated_at"].as_str()));
+    json!({ "notifications": notifications, "errors": errors })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notification(id: &str, reason: &str, updated_at: &str) -> Value {
+        json!({
+            "id": id,
+            "reason": reason,
+            "unread": true,
+            "updated_at": updated_at,
+            "repository": {"full_name": "scottidler/gx"},
+            "subject": {"type": "PullRequest", "title": "Fix", "url": "https://api.github.com/repos/scottidler/gx/pulls/1"}
+        })
+    }
+
+    #[test]
+    fn test_list_notifications_endpoint() {
+        let json = r#"{"repo": "scottidler/gx", "participating": true, "since": "2025-01-01T00:00:00+02:00"}"#;
+        let request: ListNotificationsRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            request.endpoint(),
+            "repos/scottidler/gx/notifications?participating=true&since=2025-01-01T00%3A00%3A00%2B02%3A00"
+        );
+
+        let request: ListNotificationsRequest = serde_json::from_str("{}").unwrap();
+        assert_eq!(request.endpoint(), "notifications");
+    }
+
+    #[test]
+    fn test_shape_and_merge_notifications() {
+        let home = json!([
+            notification("1", "mention", "2025-01-01T00:00:00Z"),
+            notification("2", "subscribed", "2025-01-03T00:00:00Z")
+        ]);
+        let work = json!([notification("3", "review_requested", "2025-01-02T00:00:00Z")]);
+
+        let home = shape_notifications(&home, Some("mention"), Some("home"));
+        assert_eq!(home.len(), 1);
+        assert_eq!(home[0]["account"], "home");
+        assert_eq!(home[0]["title"], "Fix");
+
+        let work = shape_notifications(&work, None, Some("work"));
+        let merged = merge_notifications(vec![
+            ("home".to_string(), Ok(home)),
+            ("oss".to_string(), Err(crate::Error::GhCli("HTTP 403".to_string()))),
+            ("work".to_string(), Ok(work)),
+        ]);
+        assert_eq!(merged["notifications"][0]["id"], "3");
+        assert_eq!(merged["notifications"][1]["id"], "1");
+        assert_eq!(merged["errors"]["oss"], "gh CLI error: HTTP 403");
+    }
+}
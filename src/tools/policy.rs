@@ -0,0 +1,62 @@
+//! Repository policy tool request types
+
+use crate::output::OutputOptions;
+use crate::policy::PolicySpec;
+use crate::{Error, Result};
+use rmcp::schemars;
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+/// Request parameters for plan_policy and apply_policy tools
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct PolicyRequest {
+    /// The account to use for targets that don't name one
+    #[schemars(
+        description = "The account to use for targets (or repos) that don't name one (e.g., 'home', 'work'). Uses default if not specified."
+    )]
+    pub account: Option<String>,
+
+    /// Path to a policy spec file
+    #[schemars(description = "Path to a YAML policy spec on the server's filesystem")]
+    pub path: Option<String>,
+
+    /// Inline policy spec
+    #[schemars(description = "Inline policy spec, instead of path")]
+    pub spec: Option<PolicySpec>,
+
+    /// Repositories to check instead of the spec's targets
+    #[schemars(description = "Repositories ('owner/name', globs allowed) to use instead of the spec's targets")]
+    pub repos: Option<Vec<String>>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+impl PolicyRequest {
+    /// The spec to plan, from `path` or `spec`, with `account` and `repos` applied
+    pub fn take_spec(&mut self) -> Result<PolicySpec> {
+        let spec = match (self.path.take(), self.spec.take()) {
+            (Some(path), None) => PolicySpec::load(shellexpand::tilde(&path).as_ref())?,
+            (None, Some(spec)) => spec,
+            _ => return Err(Error::Tool("provide exactly one of path or spec".to_string())),
+        };
+        spec.with_targets(self.account.take(), self.repos.take().unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_policy_request_take_spec() {
+        let json = r#"{"account": "work", "spec": {"targets": [{"repos": ["tatari-tv/svc"]}], "labels": []}}"#;
+        let mut request: PolicyRequest = serde_json::from_str(json).unwrap();
+        let spec = request.take_spec().unwrap();
+        assert_eq!(spec.targets[0].account.as_deref(), Some("work"));
+
+        let mut request: PolicyRequest = serde_json::from_str(r#"{"repos": ["a/b"]}"#).unwrap();
+        assert!(request.take_spec().is_err());
+    }
+}
@@ -1,8 +1,10 @@
 //! Branch protection tool request types
 
+use crate::output::OutputOptions;
+use crate::settings::BranchProtectionSettings;
 use rmcp::schemars;
 use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
+use serde::Deserialize;
 
 /// Request parameters for get_branch_protection tool
 #[derive(Debug, Deserialize, JsonSchema)]
@@ -22,34 +24,10 @@ pub struct GetBranchProtectionRequest {
     /// Branch name
     #[schemars(description = "Branch name to get protection rules for")]
     pub branch: String,
-}
-
-/// Required status checks configuration
-#[derive(Debug, Deserialize, Serialize, JsonSchema)]
-pub struct RequiredStatusChecks {
-    /// Require branches to be up to date before merging
-    #[schemars(description = "Require branches to be up to date before merging")]
-    pub strict: Option<bool>,
-
-    /// List of status check contexts that must pass
-    #[schemars(description = "List of status check contexts that must pass")]
-    pub contexts: Option<Vec<String>>,
-}
 
-/// Required pull request reviews configuration
-#[derive(Debug, Deserialize, Serialize, JsonSchema)]
-pub struct RequiredPullRequestReviews {
-    /// Number of required approving reviews
-    #[schemars(description = "Number of required approving reviews")]
-    pub required_approving_review_count: Option<u32>,
-
-    /// Dismiss stale reviews when new commits are pushed
-    #[schemars(description = "Dismiss stale reviews when new commits are pushed")]
-    pub dismiss_stale_reviews: Option<bool>,
-
-    /// Require review from code owners
-    #[schemars(description = "Require review from code owners")]
-    pub require_code_owner_reviews: Option<bool>,
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
 }
 
 /// Request parameters for set_branch_protection tool
@@ -71,37 +49,109 @@ pub struct SetBranchProtectionRequest {
     #[schemars(description = "Branch name to set protection rules for")]
     pub branch: String,
 
-    /// Require status checks to pass before merging
-    #[schemars(description = "Require status checks to pass before merging")]
-    pub required_status_checks: Option<RequiredStatusChecks>,
+    /// Protection settings
+    #[serde(flatten)]
+    pub settings: BranchProtectionSettings,
 
-    /// Enforce all configured restrictions for administrators
-    #[schemars(description = "Enforce all configured restrictions for administrators")]
-    pub enforce_admins: Option<bool>,
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
 
-    /// Require pull request reviews before merging
-    #[schemars(description = "Require pull request reviews before merging")]
-    pub required_pull_request_reviews: Option<RequiredPullRequestReviews>,
+/// Request parameters for update_branch_protection tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct UpdateBranchProtectionRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
 
-    /// Restrict who can push to the protected branch
-    #[schemars(description = "Restrict who can push to the protected branch")]
-    pub restrictions: Option<bool>,
+    /// Repository owner
+    #[schemars(description = "Repository owner (user or organization)")]
+    pub owner: String,
 
-    /// Require signed commits
-    #[schemars(description = "Require signed commits")]
-    pub required_signatures: Option<bool>,
+    /// Repository name
+    #[schemars(description = "Repository name")]
+    pub repo: String,
+
+    /// Branch name
+    #[schemars(description = "Branch name to update protection rules for")]
+    pub branch: String,
 
-    /// Require linear history
-    #[schemars(description = "Require linear history (no merge commits)")]
-    pub required_linear_history: Option<bool>,
+    /// Settings to change; unset ones keep their current value
+    #[serde(flatten)]
+    pub settings: BranchProtectionSettings,
 
-    /// Allow force pushes
-    #[schemars(description = "Allow force pushes")]
-    pub allow_force_pushes: Option<bool>,
+    /// Status check contexts to add
+    #[schemars(description = "Status check contexts to add to the required checks")]
+    pub add_status_checks: Option<Vec<String>>,
 
-    /// Allow deletions
-    #[schemars(description = "Allow branch deletions")]
-    pub allow_deletions: Option<bool>,
+    /// Status check contexts to remove
+    #[schemars(description = "Status check contexts to remove from the required checks")]
+    pub remove_status_checks: Option<Vec<String>>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+/// Request parameters for apply_branch_protection_bulk tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ApplyBranchProtectionBulkRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Explicit repositories
+    #[schemars(description = "Repositories as 'owner/name'. Alternatively select them with owner, name_glob and topic.")]
+    pub repos: Option<Vec<String>>,
+
+    /// Owner whose repositories to select
+    #[schemars(description = "User or organization whose non-archived repositories to select (instead of repos)")]
+    pub owner: Option<String>,
+
+    /// Repository name glob
+    #[schemars(description = "Only repositories whose name matches this glob, e.g. 'svc-*'")]
+    pub name_glob: Option<String>,
+
+    /// Repository topic
+    #[schemars(description = "Only repositories with this topic")]
+    pub topic: Option<String>,
+
+    /// Branch to protect
+    #[schemars(description = "Branch to protect (default: each repository's default branch)")]
+    pub branch: Option<String>,
+
+    /// Protection settings
+    #[serde(flatten)]
+    pub settings: BranchProtectionSettings,
+
+    /// Status check contexts to add
+    #[schemars(description = "Status check contexts to add to the required checks")]
+    pub add_status_checks: Option<Vec<String>>,
+
+    /// Status check contexts to remove
+    #[schemars(description = "Status check contexts to remove from the required checks")]
+    pub remove_status_checks: Option<Vec<String>>,
+
+    /// Replace instead of update
+    #[schemars(
+        description = "Replace each branch's protection with exactly these settings, like set_branch_protection (default: false, which changes only the given settings like update_branch_protection)"
+    )]
+    pub replace: Option<bool>,
+
+    /// Maximum repositories changed at once
+    #[schemars(description = "Maximum number of repositories to change at once (default: 8)")]
+    pub concurrency: Option<usize>,
+
+    /// Preview without changing anything
+    #[schemars(
+        description = "Change nothing; report each repository's current protection differences from the result, as {field: {from, to}} (default: false)"
+    )]
+    pub dry_run: Option<bool>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
 }
 
 /// Request parameters for delete_branch_protection tool
@@ -147,7 +197,16 @@ mod tests {
             }
         }"#;
         let request: SetBranchProtectionRequest = serde_json::from_str(json).unwrap();
-        assert_eq!(request.enforce_admins, Some(true));
-        assert!(request.required_pull_request_reviews.is_some());
+        assert_eq!(request.settings.enforce_admins, Some(true));
+        assert!(request.settings.required_pull_request_reviews.is_some());
+    }
+
+    #[test]
+    fn test_apply_branch_protection_bulk_request() {
+        let json = r#"{"owner": "tatari-tv", "name_glob": "svc-*", "add_status_checks": ["security-scan"], "enforce_admins": true}"#;
+        let request: ApplyBranchProtectionBulkRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.name_glob.as_deref(), Some("svc-*"));
+        assert_eq!(request.settings.enforce_admins, Some(true));
+        assert!(request.repos.is_none());
     }
 }
@@ -1,8 +1,10 @@
 //! Pull request tool request types
 
+use crate::output::OutputOptions;
 use rmcp::schemars;
 use schemars::JsonSchema;
 use serde::Deserialize;
+use serde_json::{Value, json};
 
 /// Request parameters for get_pr tool
 #[derive(Debug, Deserialize, JsonSchema)]
@@ -22,6 +24,10 @@ pub struct GetPrRequest {
     /// Pull request number
     #[schemars(description = "Pull request number")]
     pub number: u64,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
 }
 
 /// Request parameters for get_pr_diff tool
@@ -42,6 +48,24 @@ pub struct GetPrDiffRequest {
     /// Pull request number
     #[schemars(description = "Pull request number")]
     pub number: u64,
+
+    /// File paths or glob patterns to include
+    #[schemars(
+        description = "Only include files matching these paths or glob patterns (e.g. ['src/**/*.rs', 'README.md']). Matching files are included even if on the ignore list."
+    )]
+    pub files: Option<Vec<String>>,
+
+    /// Include files on the configured ignore list
+    #[schemars(
+        description = "Include lockfiles, vendored and generated files that are skipped by default (default: false)"
+    )]
+    pub include_ignored: Option<bool>,
+
+    /// Hunk to start from, for paging through large diffs
+    #[schemars(
+        description = "Return hunks starting at this 0-based index, as many as fit in the response; the footer gives the next offset"
+    )]
+    pub hunk_offset: Option<usize>,
 }
 
 /// Request parameters for get_pr_files tool
@@ -62,6 +86,208 @@ pub struct GetPrFilesRequest {
     /// Pull request number
     #[schemars(description = "Pull request number")]
     pub number: u64,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+/// Sections that get_pr_overview can include
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PrOverviewSection {
+    /// PR description
+    Body,
+    /// Label names
+    Labels,
+    /// Requested reviewers and latest review per reviewer
+    Reviews,
+    /// Status check rollup of the head commit
+    Checks,
+    /// Changed files with additions/deletions
+    Files,
+    /// Issues the PR closes
+    LinkedIssues,
+    /// Unresolved review thread count
+    Threads,
+}
+
+impl PrOverviewSection {
+    /// All sections, in output order
+    pub const ALL: [Self; 7] = [
+        Self::Body,
+        Self::Labels,
+        Self::Reviews,
+        Self::Checks,
+        Self::Files,
+        Self::LinkedIssues,
+        Self::Threads,
+    ];
+
+    /// GraphQL selection for this section
+    fn selection(self) -> &'static str {
+        match self {
+            Self::Body => "body",
+            Self::Labels => "labels(first: 50) { nodes { name } }",
+            Self::Reviews => {
+                "reviewRequests(first: 50) { nodes { requestedReviewer { ... on User { login } ... on Team { slug } } } } \
+                 latestReviews(first: 50) { nodes { author { login } state submittedAt } }"
+            }
+            Self::Checks => {
+                "commits(last: 1) { nodes { commit { statusCheckRollup { state contexts(first: 100) { totalCount nodes { \
+                 __typename ... on CheckRun { name status conclusion } ... on StatusContext { context state } } } } } } }"
+            }
+            Self::Files => "files(first: 100) { totalCount nodes { path additions deletions changeType } }",
+            Self::LinkedIssues => "closingIssuesReferences(first: 20) { nodes { number title state url } }",
+            Self::Threads => "reviewThreads(first: 100) { totalCount pageInfo { hasNextPage } nodes { isResolved } }",
+        }
+    }
+}
+
+/// Request parameters for get_pr_overview tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct GetPrOverviewRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Repository owner
+    #[schemars(description = "Repository owner (user or organization)")]
+    pub owner: String,
+
+    /// Repository name
+    #[schemars(description = "Repository name")]
+    pub repo: String,
+
+    /// Pull request number
+    #[schemars(description = "Pull request number")]
+    pub number: u64,
+
+    /// Sections to include
+    #[schemars(
+        description = "Sections to include: body, labels, reviews, checks, files, linked_issues, threads (default: all). Core fields (title, state, author, branches, mergeability, size) are always returned."
+    )]
+    pub sections: Option<Vec<PrOverviewSection>>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+/// Build the get_pr_overview GraphQL query for the given sections
+pub fn pr_overview_query(sections: &[PrOverviewSection]) -> String {
+    let selections: Vec<&str> = PrOverviewSection::ALL
+        .iter()
+        .filter(|s| sections.contains(s))
+        .map(|s| s.selection())
+        .collect();
+
+    format!(
+        "query PrOverview($owner: String!, $repo: String!, $number: Int!) {{ \
+         repository(owner: $owner, name: $repo) {{ pullRequest(number: $number) {{ \
+         number title state isDraft url author {{ login }} headRefName baseRefName \
+         mergeable mergeStateStatus reviewDecision additions deletions changedFiles {} }} }} }}",
+        selections.join(" ")
+    )
+}
+
+/// Reshape the GraphQL pullRequest object into a compact overview
+pub fn shape_pr_overview(pr: &Value) -> Value {
+    let mut overview = json!({
+        "number": pr["number"],
+        "title": pr["title"],
+        "state": pr["state"],
+        "draft": pr["isDraft"],
+        "url": pr["url"],
+        "author": pr["author"]["login"],
+        "head": pr["headRefName"],
+        "base": pr["baseRefName"],
+        "mergeable": pr["mergeable"],
+        "merge_state": pr["mergeStateStatus"],
+        "review_decision": pr["reviewDecision"],
+        "additions": pr["additions"],
+        "deletions": pr["deletions"],
+        "changed_files": pr["changedFiles"],
+    });
+
+    let nodes = |value: &Value| value["nodes"].as_array().cloned().unwrap_or_default();
+
+    if let Some(body) = pr.get("body") {
+        overview["body"] = body.clone();
+    }
+
+    if pr.get("labels").is_some() {
+        overview["labels"] = nodes(&pr["labels"]).iter().map(|l| l["name"].clone()).collect();
+    }
+
+    if pr.get("latestReviews").is_some() {
+        let requested: Vec<Value> = nodes(&pr["reviewRequests"])
+            .iter()
+            .map(|r| {
+                let reviewer = &r["requestedReviewer"];
+                reviewer.get("login").or_else(|| reviewer.get("slug")).cloned().unwrap_or(Value::Null)
+            })
+            .collect();
+        let reviews: Vec<Value> = nodes(&pr["latestReviews"])
+            .iter()
+            .map(|r| json!({"reviewer": r["author"]["login"], "state": r["state"], "submitted_at": r["submittedAt"]}))
+            .collect();
+        overview["reviewers"] = json!({"requested": requested, "reviews": reviews});
+    }
+
+    if pr.get("commits").is_some() {
+        let rollup = &pr["commits"]["nodes"][0]["commit"]["statusCheckRollup"];
+        let (mut passed, mut failed, mut pending) = (0, Vec::new(), Vec::new());
+        for check in nodes(&rollup["contexts"]) {
+            let (name, outcome) = match check["__typename"].as_str() {
+                Some("StatusContext") => (&check["context"], check["state"].as_str()),
+                _ => (&check["name"], check["conclusion"].as_str()),
+            };
+            match outcome {
+                Some("SUCCESS" | "NEUTRAL" | "SKIPPED") => passed += 1,
+                Some("FAILURE" | "ERROR" | "TIMED_OUT" | "CANCELLED" | "ACTION_REQUIRED" | "STARTUP_FAILURE") => {
+                    failed.push(name.clone())
+                }
+                _ => pending.push(name.clone()),
+            }
+        }
+        overview["checks"] = json!({
+            "state": rollup["state"],
+            "total": rollup["contexts"]["totalCount"],
+            "passed": passed,
+            "failed": failed,
+            "pending": pending,
+        });
+    }
+
+    if pr.get("files").is_some() {
+        overview["files"] = nodes(&pr["files"])
+            .iter()
+            .map(|f| json!({"path": f["path"], "additions": f["additions"], "deletions": f["deletions"], "change": f["changeType"]}))
+            .collect();
+        if pr["files"]["totalCount"].as_u64() > Some(nodes(&pr["files"]).len() as u64) {
+            overview["files_truncated"] = json!(true);
+        }
+    }
+
+    if pr.get("closingIssuesReferences").is_some() {
+        overview["linked_issues"] = nodes(&pr["closingIssuesReferences"]).into();
+    }
+
+    if pr.get("reviewThreads").is_some() {
+        let unresolved = nodes(&pr["reviewThreads"])
+            .iter()
+            .filter(|t| t["isResolved"] == json!(false))
+            .count();
+        overview["unresolved_threads"] = json!(unresolved);
+        overview["total_threads"] = pr["reviewThreads"]["totalCount"].clone();
+        // Only the first 100 threads are fetched, so the unresolved count is a lower bound
+        if pr["reviewThreads"]["pageInfo"]["hasNextPage"] == json!(true) {
+            overview["threads_truncated"] = json!(true);
+        }
+    }
+
+    overview
 }
 
 /// Request parameters for list_prs tool
@@ -94,6 +320,10 @@ pub struct ListPrsRequest {
     /// Filter by head branch
     #[schemars(description = "Filter by head branch")]
     pub head: Option<String>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
 }
 
 /// Request parameters for search_prs tool
@@ -110,6 +340,16 @@ pub struct SearchPrsRequest {
     /// Maximum number of results
     #[schemars(description = "Maximum number of results (default: 30)")]
     pub limit: Option<u32>,
+
+    /// Search with every configured account
+    #[schemars(
+        description = "Run the search under every configured account, merging results by URL and listing which accounts can see each hit (default: false)"
+    )]
+    pub all_accounts: Option<bool>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
 }
 
 /// Request parameters for create_pr tool
@@ -146,6 +386,10 @@ pub struct CreatePrRequest {
     /// Create as draft PR
     #[schemars(description = "Create as draft PR")]
     pub draft: Option<bool>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
 }
 
 /// Request parameters for edit_pr tool
@@ -178,6 +422,10 @@ pub struct EditPrRequest {
     /// New base branch
     #[schemars(description = "New base branch")]
     pub base: Option<String>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
 }
 
 /// Request parameters for merge_pr tool
@@ -210,6 +458,10 @@ pub struct MergePrRequest {
     /// Custom commit message for squash/merge
     #[schemars(description = "Custom commit message")]
     pub commit_message: Option<String>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
 }
 
 /// Request parameters for close_pr tool
@@ -230,6 +482,10 @@ pub struct ClosePrRequest {
     /// Pull request number
     #[schemars(description = "Pull request number")]
     pub number: u64,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
 }
 
 /// Request parameters for comment_pr tool
@@ -254,6 +510,10 @@ pub struct CommentPrRequest {
     /// Comment body
     #[schemars(description = "Comment body (Markdown supported)")]
     pub body: String,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
 }
 
 #[cfg(test)]
@@ -282,4 +542,58 @@ mod tests {
         assert_eq!(request.method, Some("squash".to_string()));
         assert_eq!(request.delete_branch, Some(true));
     }
+
+    #[test]
+    fn test_get_pr_overview_request() {
+        let json = r#"{"owner": "scottidler", "repo": "gx", "number": 42, "sections": ["checks", "linked_issues"]}"#;
+        let request: GetPrOverviewRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            request.sections,
+            Some(vec![PrOverviewSection::Checks, PrOverviewSection::LinkedIssues])
+        );
+    }
+
+    #[test]
+    fn test_pr_overview_query_sections() {
+        let query = pr_overview_query(&[PrOverviewSection::Labels]);
+        assert!(query.contains("labels(first: 50)"));
+        assert!(!query.contains("statusCheckRollup"));
+        assert!(!query.contains(" body "));
+    }
+
+    #[test]
+    fn test_shape_pr_overview() {
+        let pr = json!({
+            "number": 42,
+            "title": "Add feature",
+            "author": {"login": "scottidler"},
+            "labels": {"nodes": [{"name": "bug"}]},
+            "commits": {"nodes": [{"commit": {"statusCheckRollup": {
+                "state": "FAILURE",
+                "contexts": {"totalCount": 3, "nodes": [
+                    {"__typename": "CheckRun", "name": "test", "status": "COMPLETED", "conclusion": "FAILURE"},
+                    {"__typename": "CheckRun", "name": "lint", "status": "COMPLETED", "conclusion": "SUCCESS"},
+                    {"__typename": "StatusContext", "context": "ci/deploy", "state": "PENDING"}
+                ]}
+            }}}]},
+            "reviewThreads": {"totalCount": 2, "nodes": [{"isResolved": false}, {"isResolved": true}]}
+        });
+
+        let overview = shape_pr_overview(&pr);
+        assert_eq!(overview["author"], "scottidler");
+        assert_eq!(overview["labels"], json!(["bug"]));
+        assert_eq!(overview["checks"]["passed"], 1);
+        assert_eq!(overview["checks"]["failed"], json!(["test"]));
+        assert_eq!(overview["checks"]["pending"], json!(["ci/deploy"]));
+        assert_eq!(overview["unresolved_threads"], 1);
+        assert!(overview.get("threads_truncated").is_none());
+        assert!(overview.get("files").is_none());
+
+        let pr = json!({
+            "reviewThreads": {"totalCount": 150, "pageInfo": {"hasNextPage": true}, "nodes": [{"isResolved": false}]}
+        });
+        let overview = shape_pr_overview(&pr);
+        assert_eq!(overview["total_threads"], 150);
+        assert_eq!(overview["threads_truncated"], true);
+    }
 }
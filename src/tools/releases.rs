@@ -1,5 +1,6 @@
 //! Release tool request types
 
+use crate::output::OutputOptions;
 use rmcp::schemars;
 use schemars::JsonSchema;
 use serde::Deserialize;
@@ -22,6 +23,10 @@ pub struct ListReleasesRequest {
     /// Maximum number of releases to return
     #[schemars(description = "Maximum number of releases to return (default: 30)")]
     pub limit: Option<u32>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
 }
 
 /// Request parameters for get_release tool
@@ -42,6 +47,10 @@ pub struct GetReleaseRequest {
     /// Release tag (e.g., 'v1.0.0')
     #[schemars(description = "Release tag (e.g., 'v1.0.0')")]
     pub tag: String,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
 }
 
 /// Request parameters for create_release tool
@@ -86,6 +95,10 @@ pub struct CreateReleaseRequest {
     /// Auto-generate release notes
     #[schemars(description = "Auto-generate release notes from commits")]
     pub generate_notes: Option<bool>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
 }
 
 /// Request parameters for delete_release tool
@@ -110,6 +123,10 @@ pub struct DeleteReleaseRequest {
     /// Also delete the associated tag
     #[schemars(description = "Also delete the associated git tag")]
     pub delete_tag: Option<bool>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
 }
 
 /// Request parameters for list_release_assets tool
@@ -130,6 +147,10 @@ pub struct ListReleaseAssetsRequest {
     /// Release tag
     #[schemars(description = "Release tag (e.g., 'v1.0.0')")]
     pub tag: String,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
 }
 
 /// Request parameters for download_release_asset tool
@@ -158,6 +179,10 @@ pub struct DownloadReleaseAssetRequest {
     /// Directory to download to
     #[schemars(description = "Directory to download assets to (default: current directory)")]
     pub dir: Option<String>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
 }
 
 #[cfg(test)]
@@ -1,8 +1,11 @@
 //! Repository-related tool request types
 
+use crate::output::OutputOptions;
+use crate::settings::RepoSettings;
 use rmcp::schemars;
 use schemars::JsonSchema;
 use serde::Deserialize;
+use serde_json::{Value, json};
 
 /// Request parameters for create_repo tool
 #[derive(Debug, Deserialize, JsonSchema)]
@@ -26,6 +29,109 @@ pub struct CreateRepoRequest {
     /// Organization to create the repo in (omit for personal repo)
     #[schemars(description = "Organization to create the repo in (omit for personal repo)")]
     pub org: Option<String>,
+
+    /// Homepage URL
+    #[schemars(description = "Homepage URL")]
+    pub homepage: Option<String>,
+
+    /// Create an initial commit with a README
+    #[schemars(description = "Create an initial commit with a README (default: false)")]
+    pub auto_init: Option<bool>,
+
+    /// .gitignore template
+    #[schemars(description = ".gitignore template to add, e.g. 'Rust' or 'Python' (implies an initial commit)")]
+    pub gitignore_template: Option<String>,
+
+    /// License template
+    #[schemars(description = "License keyword to add, e.g. 'mit' or 'apache-2.0' (implies an initial commit)")]
+    pub license_template: Option<String>,
+
+    /// Team to grant access
+    #[schemars(description = "ID of a team in the organization to grant access (org repos only)")]
+    pub team_id: Option<u64>,
+
+    /// Apply the account's repo profile
+    #[schemars(
+        description = "Apply the account's configured repo profile (merge options, default branch protection) after creation (default: true)"
+    )]
+    pub apply_profile: Option<bool>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+impl CreateRepoRequest {
+    /// Request body for `POST user/repos` or `POST orgs/{org}/repos`
+    pub fn to_body(&self) -> Value {
+        let mut body = json!({
+            "name": self.name,
+            "private": self.private.unwrap_or(false),
+        });
+        for (key, value) in [
+            ("description", &self.description),
+            ("homepage", &self.homepage),
+            ("gitignore_template", &self.gitignore_template),
+            ("license_template", &self.license_template),
+        ] {
+            if let Some(value) = value {
+                body[key] = json!(value);
+            }
+        }
+        if let Some(auto_init) = self.auto_init {
+            body["auto_init"] = json!(auto_init);
+        }
+        if let Some(team_id) = self.team_id {
+            body["team_id"] = json!(team_id);
+        }
+        body
+    }
+}
+
+/// Request parameters for create_repo_from_template tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct CreateRepoFromTemplateRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Template repository owner
+    #[schemars(description = "Owner of the template repository")]
+    pub template_owner: String,
+
+    /// Template repository name
+    #[schemars(description = "Name of the template repository")]
+    pub template_repo: String,
+
+    /// Owner of the new repository
+    #[schemars(description = "User or organization to create the repository in (default: the authenticated user)")]
+    pub owner: Option<String>,
+
+    /// Name of the new repository
+    #[schemars(description = "Name of the repository to create")]
+    pub name: String,
+
+    /// Description of the repository
+    #[schemars(description = "Description of the repository")]
+    pub description: Option<String>,
+
+    /// Whether the repository should be private
+    #[schemars(description = "Whether the repository should be private (default: false)")]
+    pub private: Option<bool>,
+
+    /// Copy all branches
+    #[schemars(description = "Copy all branches of the template, not just the default branch (default: false)")]
+    pub include_all_branches: Option<bool>,
+
+    /// Apply the account's repo profile
+    #[schemars(
+        description = "Apply the account's configured repo profile (merge options, default branch protection) after creation (default: true)"
+    )]
+    pub apply_profile: Option<bool>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
 }
 
 /// Request parameters for list_repos tool
@@ -42,6 +148,10 @@ pub struct ListReposRequest {
     /// Maximum number of repos to return
     #[schemars(description = "Maximum number of repos to return (default: 30)")]
     pub limit: Option<u32>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
 }
 
 /// Request parameters for get_repo tool
@@ -58,6 +168,10 @@ pub struct GetRepoRequest {
     /// Repository name
     #[schemars(description = "Repository name")]
     pub repo: String,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
 }
 
 /// Request parameters for archive_repo tool
@@ -74,6 +188,114 @@ pub struct ArchiveRepoRequest {
     /// Repository name
     #[schemars(description = "Repository name")]
     pub repo: String,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+/// Request parameters for unarchive_repo tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct UnarchiveRepoRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Repository owner
+    #[schemars(description = "Repository owner (user or organization)")]
+    pub owner: String,
+
+    /// Repository name
+    #[schemars(description = "Repository name")]
+    pub repo: String,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+/// Request parameters for update_repo tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct UpdateRepoRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Repository owner
+    #[schemars(description = "Repository owner (user or organization)")]
+    pub owner: String,
+
+    /// Repository name
+    #[schemars(description = "Repository name")]
+    pub repo: String,
+
+    /// Settings to change
+    #[serde(flatten)]
+    pub settings: RepoSettings,
+
+    /// Topics (replaces the existing set)
+    #[schemars(description = "Topics; replaces the repository's existing topics")]
+    pub topics: Option<Vec<String>>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+/// Request parameters for rename_repo tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct RenameRepoRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Repository owner
+    #[schemars(description = "Repository owner (user or organization)")]
+    pub owner: String,
+
+    /// Repository name
+    #[schemars(description = "Current repository name")]
+    pub repo: String,
+
+    /// New repository name
+    #[schemars(description = "New repository name. GitHub redirects the old name.")]
+    pub new_name: String,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+/// Request parameters for transfer_repo tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct TransferRepoRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Repository owner
+    #[schemars(description = "Current repository owner (user or organization)")]
+    pub owner: String,
+
+    /// Repository name
+    #[schemars(description = "Repository name")]
+    pub repo: String,
+
+    /// New owner
+    #[schemars(description = "User or organization to transfer the repository to")]
+    pub new_owner: String,
+
+    /// New repository name
+    #[schemars(description = "Name for the repository under the new owner (default: unchanged)")]
+    pub new_name: Option<String>,
+
+    /// Team IDs to grant access
+    #[schemars(description = "IDs of teams in the new organization to grant access")]
+    pub team_ids: Option<Vec<u64>>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
 }
 
 #[cfg(test)]
@@ -88,6 +310,16 @@ mod tests {
         assert_eq!(request.private, Some(true));
     }
 
+    #[test]
+    fn test_create_repo_body() {
+        let json = r#"{"name": "svc", "org": "tatari-tv", "private": true, "license_template": "mit", "team_id": 42}"#;
+        let request: CreateRepoRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            request.to_body(),
+            json!({"name": "svc", "private": true, "license_template": "mit", "team_id": 42})
+        );
+    }
+
     #[test]
     fn test_get_repo_request() {
         let json = r#"{"owner": "scottidler", "repo": "gx"}"#;
@@ -95,4 +327,18 @@ mod tests {
         assert_eq!(request.owner, "scottidler");
         assert_eq!(request.repo, "gx");
     }
+
+    #[test]
+    fn test_update_repo_request_body() {
+        let json = r#"{"owner": "scottidler", "repo": "gx", "description": "Git tools", "has_wiki": false, "delete_branch_on_merge": true, "topics": ["git"], "fields": ["name"]}"#;
+        let request: UpdateRepoRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.topics, Some(vec!["git".to_string()]));
+        assert_eq!(request.output.fields, Some(vec!["name".to_string()]));
+
+        let body = serde_json::to_value(&request.settings).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({"description": "Git tools", "has_wiki": false, "delete_branch_on_merge": true})
+        );
+    }
 }
@@ -0,0 +1,528 @@
+//! Repository and organization ruleset tool request types
+
+use crate::output::OutputOptions;
+use crate::{Error, Result};
+use rmcp::schemars;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+/// Where a ruleset lives: a repository, or an organization when repo is omitted
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct RulesetScope {
+    /// Repository owner, or the organization for org rulesets
+    #[schemars(description = "Repository owner, or the organization for org-level rulesets")]
+    pub owner: String,
+
+    /// Repository name
+    #[schemars(description = "Repository name; omit for org-level rulesets")]
+    pub repo: Option<String>,
+}
+
+impl RulesetScope {
+    /// Rulesets collection endpoint
+    pub fn collection(&self) -> String {
+        match self.repo {
+            Some(ref repo) => format!("repos/{}/{repo}/rulesets", self.owner),
+            None => format!("orgs/{}/rulesets", self.owner),
+        }
+    }
+}
+
+/// What a ruleset applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RulesetTarget {
+    /// Branches
+    Branch,
+    /// Tags
+    Tag,
+    /// Pushes (org rulesets only)
+    Push,
+}
+
+/// Whether a ruleset is enforced
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Enforcement {
+    /// Enforced
+    Active,
+    /// Evaluated and reported but not enforced
+    Evaluate,
+    /// Not enforced
+    Disabled,
+}
+
+/// Kind of actor that can bypass a ruleset
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+pub enum BypassActorType {
+    /// A GitHub App
+    Integration,
+    /// Organization admins
+    OrganizationAdmin,
+    /// A repository role
+    RepositoryRole,
+    /// A team
+    Team,
+    /// Deploy keys
+    DeployKey,
+}
+
+/// An actor allowed to bypass a ruleset
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct BypassActor {
+    /// Actor ID
+    #[schemars(
+        description = "ID of the team, app or repository role (e.g. 5 for the admin role); omit for OrganizationAdmin and DeployKey"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actor_id: Option<u64>,
+
+    /// Actor type
+    #[schemars(description = "Integration, OrganizationAdmin, RepositoryRole, Team or DeployKey")]
+    pub actor_type: BypassActorType,
+
+    /// When the actor may bypass
+    #[schemars(description = "always or pull_request (bypass only through pull requests); default: always")]
+    #[serde(default = "default_bypass_mode")]
+    pub bypass_mode: String,
+}
+
+fn default_bypass_mode() -> String {
+    "always".to_string()
+}
+
+/// Parameters of a pull_request rule
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+#[serde(default)]
+pub struct PullRequestRule {
+    /// Number of required approving reviews
+    #[schemars(description = "Number of approving reviews required (default: 0)")]
+    pub required_approving_review_count: u32,
+
+    /// Dismiss stale approvals on push
+    #[schemars(description = "Dismiss approvals when new commits are pushed")]
+    pub dismiss_stale_reviews_on_push: bool,
+
+    /// Require code owner review
+    #[schemars(description = "Require an approving review from a code owner of changed files")]
+    pub require_code_owner_review: bool,
+
+    /// Require approval of the most recent push
+    #[schemars(description = "Require the most recent push to be approved by someone other than its author")]
+    pub require_last_push_approval: bool,
+
+    /// Require conversation resolution
+    #[schemars(description = "Require all review threads to be resolved before merging")]
+    pub required_review_thread_resolution: bool,
+}
+
+/// A status check required by a required_status_checks rule
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct StatusCheck {
+    /// Check name
+    #[schemars(description = "Status check context (name)")]
+    pub context: String,
+
+    /// App that must provide the check
+    #[schemars(description = "ID of the GitHub App that must provide the check (default: any)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub integration_id: Option<u64>,
+}
+
+/// Parameters of a required_status_checks rule
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct StatusChecksRule {
+    /// Checks that must pass
+    #[schemars(description = "Status checks that must pass")]
+    pub required_status_checks: Vec<StatusCheck>,
+
+    /// Require branches to be up to date
+    #[schemars(description = "Require branches to be up to date with the base before merging (default: false)")]
+    #[serde(default)]
+    pub strict_required_status_checks_policy: bool,
+}
+
+/// Parameters of a required_deployments rule
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct DeploymentsRule {
+    /// Environments that must deploy successfully
+    #[schemars(description = "Environments that must be successfully deployed to before merging")]
+    pub required_deployment_environments: Vec<String>,
+}
+
+/// Parameters of a name or commit message pattern rule
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct PatternRule {
+    /// How to match
+    #[schemars(description = "starts_with, ends_with, contains or regex")]
+    pub operator: String,
+
+    /// Pattern to match
+    #[schemars(description = "Pattern to match")]
+    pub pattern: String,
+
+    /// Invert the match
+    #[schemars(description = "Require the pattern NOT to match (default: false)")]
+    #[serde(default)]
+    pub negate: bool,
+
+    /// Rule name
+    #[schemars(description = "Display name for the rule")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+/// A ruleset rule, in the `{type, parameters}` shape the API uses
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(tag = "type", content = "parameters", rename_all = "snake_case")]
+pub enum Rule {
+    /// Only bypass actors can create matching refs
+    Creation,
+    /// Only bypass actors can update matching refs
+    Update,
+    /// Only bypass actors can delete matching refs
+    Deletion,
+    /// Prevent merge commits
+    RequiredLinearHistory,
+    /// Require signed commits
+    RequiredSignatures,
+    /// Prevent force pushes
+    NonFastForward,
+    /// Require a pull request before merging
+    PullRequest(PullRequestRule),
+    /// Require status checks to pass
+    RequiredStatusChecks(StatusChecksRule),
+    /// Require deployments to succeed
+    RequiredDeployments(DeploymentsRule),
+    /// Restrict branch names
+    BranchNamePattern(PatternRule),
+    /// Restrict tag names
+    TagNamePattern(PatternRule),
+    /// Restrict commit messages
+    CommitMessagePattern(PatternRule),
+}
+
+/// Ruleset fields shared by create_ruleset and update_ruleset
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+pub struct RulesetSettings {
+    /// Ruleset name
+    #[schemars(description = "Ruleset name (required when creating)")]
+    pub name: Option<String>,
+
+    /// What the ruleset applies to
+    #[schemars(description = "What the ruleset applies to: branch, tag or push (default: branch)")]
+    pub target: Option<RulesetTarget>,
+
+    /// Enforcement status
+    #[schemars(description = "Enforcement: active, evaluate or disabled (default: active)")]
+    pub enforcement: Option<Enforcement>,
+
+    /// Refs to include
+    #[schemars(
+        description = "Branch or tag name patterns to include, e.g. 'main', 'release/*', '~DEFAULT_BRANCH' or '~ALL'. Bare names are qualified with refs/heads/ or refs/tags/ by target."
+    )]
+    pub include_refs: Option<Vec<String>>,
+
+    /// Refs to exclude
+    #[schemars(description = "Branch or tag name patterns to exclude")]
+    pub exclude_refs: Option<Vec<String>>,
+
+    /// Repositories to include (org rulesets)
+    #[schemars(description = "Org rulesets only: repository name patterns to include, e.g. '~ALL' or 'svc-*'")]
+    pub include_repos: Option<Vec<String>>,
+
+    /// Repositories to exclude (org rulesets)
+    #[schemars(description = "Org rulesets only: repository name patterns to exclude")]
+    pub exclude_repos: Option<Vec<String>>,
+
+    /// Actors that can bypass the ruleset
+    #[schemars(description = "Actors that can bypass the ruleset; replaces the existing list")]
+    pub bypass_actors: Option<Vec<BypassActor>>,
+
+    /// Rules
+    #[schemars(
+        description = "Rules, each {type, parameters}: creation, update, deletion, required_linear_history, required_signatures, non_fast_forward, pull_request, required_status_checks, required_deployments, branch_name_pattern, tag_name_pattern, commit_message_pattern. Replaces the existing rules."
+    )]
+    pub rules: Option<Vec<Rule>>,
+}
+
+impl RulesetSettings {
+    /// Request body for creating a ruleset; unset fields are left out
+    pub fn to_body(&self, org: bool) -> Result<Value> {
+        self.overlay(org, &Value::Null)
+    }
+
+    /// Request body for updating (`PUT`) the `current` ruleset.
+    ///
+    /// GitHub replaces a condition as a whole, so an include or exclude list that isn't given is
+    /// carried over from the current ruleset, and bare refs are qualified by its target.
+    pub fn update_body(&self, org: bool, current: &Value) -> Result<Value> {
+        self.overlay(org, current)
+    }
+
+    fn overlay(&self, org: bool, current: &Value) -> Result<Value> {
+        let mut body = json!({});
+        if let Some(ref name) = self.name {
+            body["name"] = json!(name);
+        }
+        if let Some(target) = self.target {
+            body["target"] = json!(target);
+        }
+        if let Some(enforcement) = self.enforcement {
+            body["enforcement"] = json!(enforcement);
+        }
+        if let Some(ref actors) = self.bypass_actors {
+            body["bypass_actors"] = json!(actors);
+        }
+        if let Some(ref rules) = self.rules {
+            body["rules"] = json!(rules);
+        }
+
+        let target = self
+            .target
+            .or_else(|| serde_json::from_value(current["target"].clone()).ok())
+            .unwrap_or(RulesetTarget::Branch);
+        let refs = |patterns: &Option<Vec<String>>| {
+            patterns
+                .as_ref()
+                .map(|patterns| patterns.iter().map(|p| qualify_ref(p, target)).collect())
+        };
+        if self.include_refs.is_some() || self.exclude_refs.is_some() {
+            body["conditions"]["ref_name"] =
+                condition(&current["conditions"]["ref_name"], refs(&self.include_refs), refs(&self.exclude_refs));
+        }
+        if self.include_repos.is_some() || self.exclude_repos.is_some() {
+            if !org {
+                return Err(Error::Tool("include_repos and exclude_repos apply to org rulesets only".to_string()));
+            }
+            body["conditions"]["repository_name"] = condition(
+                &current["conditions"]["repository_name"],
+                self.include_repos.clone(),
+                self.exclude_repos.clone(),
+            );
+        }
+        Ok(body)
+    }
+
+    /// Request body for creating a ruleset: `to_body` with a branch target and active enforcement by default
+    pub fn create_body(&self, org: bool) -> Result<Value> {
+        let mut body = self.to_body(org)?;
+        if body.get("target").is_none() {
+            body["target"] = json!(RulesetTarget::Branch);
+        }
+        if body.get("enforcement").is_none() {
+            body["enforcement"] = json!(Enforcement::Active);
+        }
+        Ok(body)
+    }
+}
+
+/// An include/exclude condition, keeping the `current` condition's list where one isn't given
+fn condition(current: &Value, include: Option<Vec<String>>, exclude: Option<Vec<String>>) -> Value {
+    let list = |given: Option<Vec<String>>, key: &str| match given {
+        Some(patterns) => json!(patterns),
+        None => current.get(key).cloned().unwrap_or_else(|| json!([])),
+    };
+    json!({ "include": list(include, "include"), "exclude": list(exclude, "exclude") })
+}
+
+/// Qualify a bare branch or tag pattern with `refs/heads/` or `refs/tags/`.
+///
+/// Full refs and the special `~DEFAULT_BRANCH` and `~ALL` patterns are kept as-is.
+pub fn qualify_ref(pattern: &str, target: RulesetTarget) -> String {
+    if pattern.starts_with("refs/") || pattern.starts_with('~') {
+        return pattern.to_string();
+    }
+    match target {
+        RulesetTarget::Tag => format!("refs/tags/{pattern}"),
+        RulesetTarget::Branch | RulesetTarget::Push => format!("refs/heads/{pattern}"),
+    }
+}
+
+/// Request parameters for list_rulesets tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ListRulesetsRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Scope
+    #[serde(flatten)]
+    pub scope: RulesetScope,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+/// Request parameters for get_ruleset and delete_ruleset tools
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct RulesetRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Scope
+    #[serde(flatten)]
+    pub scope: RulesetScope,
+
+    /// Ruleset ID
+    #[schemars(description = "Ruleset ID")]
+    pub ruleset_id: u64,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+/// Request parameters for create_ruleset tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct CreateRulesetRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Scope
+    #[serde(flatten)]
+    pub scope: RulesetScope,
+
+    /// Ruleset settings
+    #[serde(flatten)]
+    pub settings: RulesetSettings,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+/// Request parameters for update_ruleset tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct UpdateRulesetRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Scope
+    #[serde(flatten)]
+    pub scope: RulesetScope,
+
+    /// Ruleset ID
+    #[schemars(description = "Ruleset ID")]
+    pub ruleset_id: u64,
+
+    /// Ruleset settings
+    #[serde(flatten)]
+    pub settings: RulesetSettings,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+/// Request parameters for get_rules_for_branch tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct GetRulesForBranchRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Repository owner
+    #[schemars(description = "Repository owner (user or organization)")]
+    pub owner: String,
+
+    /// Repository name
+    #[schemars(description = "Repository name")]
+    pub repo: String,
+
+    /// Branch name
+    #[schemars(description = "Branch name")]
+    pub branch: String,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rule_shape() {
+        let rules: Vec<Rule> = serde_json::from_value(json!([
+            {"type": "non_fast_forward"},
+            {"type": "pull_request", "parameters": {"required_approving_review_count": 2}},
+            {"type": "tag_name_pattern", "parameters": {"operator": "regex", "pattern": "^v\\d+"}}
+        ]))
+        .unwrap();
+        assert_eq!(
+            json!(rules),
+            json!([
+                {"type": "non_fast_forward"},
+                {"type": "pull_request", "parameters": {
+                    "required_approving_review_count": 2,
+                    "dismiss_stale_reviews_on_push": false,
+                    "require_code_owner_review": false,
+                    "require_last_push_approval": false,
+                    "required_review_thread_resolution": false
+                }},
+                {"type": "tag_name_pattern", "parameters": {"operator": "regex", "pattern": "^v\\d+", "negate": false}}
+            ])
+        );
+    }
+
+    #[test]
+    fn test_ruleset_body() {
+        let json = r#"{
+            "owner": "tatari-tv", "name": "protect-main", "enforcement": "active",
+            "include_refs": ["~DEFAULT_BRANCH", "release/*"], "include_repos": ["~ALL"],
+            "bypass_actors": [{"actor_type": "OrganizationAdmin"}],
+            "rules": [{"type": "deletion"}]
+        }"#;
+        let request: CreateRulesetRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.scope.collection(), "orgs/tatari-tv/rulesets");
+        assert_eq!(
+            request.settings.to_body(true).unwrap(),
+            json!({
+                "name": "protect-main",
+                "enforcement": "active",
+                "bypass_actors": [{"actor_type": "OrganizationAdmin", "bypass_mode": "always"}],
+                "rules": [{"type": "deletion"}],
+                "conditions": {
+                    "ref_name": {"include": ["~DEFAULT_BRANCH", "refs/heads/release/*"], "exclude": []},
+                    "repository_name": {"include": ["~ALL"], "exclude": []}
+                }
+            })
+        );
+        assert!(request.settings.to_body(false).is_err());
+        assert_eq!(qualify_ref("v*", RulesetTarget::Tag), "refs/tags/v*");
+    }
+
+    #[test]
+    fn test_ruleset_update_body_keeps_other_condition_lists() {
+        let current = json!({
+            "id": 42,
+            "target": "tag",
+            "conditions": {
+                "ref_name": {"include": ["refs/tags/v*"], "exclude": []},
+                "repository_name": {"include": ["svc-*"], "exclude": ["svc-legacy"], "protected": false}
+            }
+        });
+        let json = r#"{"owner": "tatari-tv", "ruleset_id": 42, "exclude_refs": ["v0*"], "include_repos": ["~ALL"]}"#;
+        let request: UpdateRulesetRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            request.settings.update_body(true, &current).unwrap(),
+            json!({
+                "conditions": {
+                    "ref_name": {"include": ["refs/tags/v*"], "exclude": ["refs/tags/v0*"]},
+                    "repository_name": {"include": ["~ALL"], "exclude": ["svc-legacy"]}
+                }
+            })
+        );
+
+        let json = r#"{"owner": "o", "repo": "r", "ruleset_id": 1, "enforcement": "evaluate"}"#;
+        let request: UpdateRulesetRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.settings.update_body(false, &current).unwrap(), json!({"enforcement": "evaluate"}));
+    }
+}
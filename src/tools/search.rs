@@ -0,0 +1,94 @@
+//! Cross-account search tool request types
+
+use crate::output::OutputOptions;
+use rmcp::schemars;
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+/// What to search for
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchKind {
+    #[default]
+    Prs,
+    Issues,
+    Code,
+    Repos,
+}
+
+impl SearchKind {
+    /// `gh search` subcommand
+    pub fn command(self) -> &'static str {
+        match self {
+            Self::Prs => "prs",
+            Self::Issues => "issues",
+            Self::Code => "code",
+            Self::Repos => "repos",
+        }
+    }
+
+    /// Fields requested with `--json`
+    pub fn json_fields(self) -> &'static str {
+        match self {
+            Self::Prs | Self::Issues => "number,title,state,author,repository,createdAt,updatedAt,url",
+            Self::Code => "path,repository,textMatches,url",
+            Self::Repos => "fullName,description,visibility,updatedAt,url",
+        }
+    }
+
+    /// `gh search` arguments for a query
+    pub fn args(self, query: &str, limit: Option<u32>) -> Vec<String> {
+        let mut args = vec!["search".to_string(), self.command().to_string(), query.to_string()];
+        if let Some(limit) = limit {
+            args.push("--limit".to_string());
+            args.push(limit.to_string());
+        }
+        args.push("--json".to_string());
+        args.push(self.json_fields().to_string());
+        args
+    }
+}
+
+/// Request parameters for search_everywhere tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SearchEverywhereRequest {
+    /// Search query (GitHub search syntax)
+    #[schemars(description = "Search query using GitHub search syntax")]
+    pub query: String,
+
+    /// What to search for
+    #[schemars(description = "What to search: prs, issues, code or repos (default: prs)")]
+    pub kind: Option<SearchKind>,
+
+    /// Maximum number of results per account
+    #[schemars(description = "Maximum number of results per account (default: 30)")]
+    pub limit: Option<u32>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_everywhere_request() {
+        let json = r#"{"query": "is:open author:@me", "kind": "issues", "limit": 5}"#;
+        let request: SearchEverywhereRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.kind, Some(SearchKind::Issues));
+        assert_eq!(
+            SearchKind::Issues.args(&request.query, request.limit),
+            vec![
+                "search",
+                "issues",
+                "is:open author:@me",
+                "--limit",
+                "5",
+                "--json",
+                "number,title,state,author,repository,createdAt,updatedAt,url"
+            ]
+        );
+    }
+}
@@ -1,5 +1,6 @@
 //! Tag tool request types
 
+use crate::output::OutputOptions;
 use rmcp::schemars;
 use schemars::JsonSchema;
 use serde::Deserialize;
@@ -22,6 +23,10 @@ pub struct ListTagsRequest {
     /// Maximum number of tags to return
     #[schemars(description = "Maximum number of tags to return (default: 30)")]
     pub limit: Option<u32>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
 }
 
 /// Request parameters for create_tag tool
@@ -50,6 +55,10 @@ pub struct CreateTagRequest {
     /// Tag message (creates annotated tag if provided)
     #[schemars(description = "Tag message (creates annotated tag if provided)")]
     pub message: Option<String>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
 }
 
 /// Request parameters for delete_tag tool
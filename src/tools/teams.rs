@@ -1,5 +1,6 @@
 //! Teams and collaborator tool request types
 
+use crate::output::OutputOptions;
 use rmcp::schemars;
 use schemars::JsonSchema;
 use serde::Deserialize;
@@ -22,6 +23,10 @@ pub struct ListCollaboratorsRequest {
     /// Filter by affiliation (outside, direct, all)
     #[schemars(description = "Filter by affiliation: outside, direct, all (default: all)")]
     pub affiliation: Option<String>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
 }
 
 /// Request parameters for add_collaborator tool
@@ -46,6 +51,10 @@ pub struct AddCollaboratorRequest {
     /// Permission level: pull, push, admin, maintain, triage
     #[schemars(description = "Permission level: pull, push, admin, maintain, triage (default: push)")]
     pub permission: Option<String>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
 }
 
 /// Request parameters for remove_collaborator tool
@@ -82,6 +91,10 @@ pub struct ListTeamsRequest {
     /// Maximum number of teams to return
     #[schemars(description = "Maximum number of teams to return (default: 30)")]
     pub limit: Option<u32>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
 }
 
 /// Request parameters for get_team_members tool
@@ -102,6 +115,10 @@ pub struct GetTeamMembersRequest {
     /// Filter by role: member, maintainer, all
     #[schemars(description = "Filter by role: member, maintainer, all (default: all)")]
     pub role: Option<String>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
 }
 
 #[cfg(test)]
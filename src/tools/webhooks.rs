@@ -0,0 +1,254 @@
+//! Webhook and webhook delivery tool request types
+
+use crate::output::OutputOptions;
+use crate::secret::SecretString;
+use rmcp::schemars;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+/// Where a webhook lives: a repository, or an organization when repo is omitted
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct HookScope {
+    /// Repository owner, or the organization for org webhooks
+    #[schemars(description = "Repository owner, or the organization for org-level webhooks")]
+    pub owner: String,
+
+    /// Repository name
+    #[schemars(description = "Repository name; omit for org-level webhooks")]
+    pub repo: Option<String>,
+}
+
+impl HookScope {
+    /// Webhooks collection endpoint
+    pub fn collection(&self) -> String {
+        match self.repo {
+            Some(ref repo) => format!("repos/{}/{repo}/hooks", self.owner),
+            None => format!("orgs/{}/hooks", self.owner),
+        }
+    }
+}
+
+/// Request parameters for list_webhooks tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ListWebhooksRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Scope
+    #[serde(flatten)]
+    pub scope: HookScope,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+/// Webhook delivery settings
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+pub struct HookConfig {
+    /// Payload URL
+    #[schemars(description = "URL the payloads are delivered to")]
+    pub url: Option<String>,
+
+    /// Payload format
+    #[schemars(description = "Payload format: json or form (default: json)")]
+    pub content_type: Option<String>,
+
+    /// Signing secret
+    #[schemars(description = "Secret used to sign payloads. It is never logged or returned.")]
+    pub secret: Option<SecretString>,
+
+    /// Skip TLS verification
+    #[schemars(description = "Skip TLS certificate verification of the payload URL (default: false)")]
+    pub insecure_ssl: Option<bool>,
+}
+
+impl HookConfig {
+    /// Config object for the hooks API; unset fields are left out
+    pub fn to_body(&self) -> Value {
+        let mut config = json!({});
+        if let Some(ref url) = self.url {
+            config["url"] = json!(url);
+        }
+        if let Some(ref content_type) = self.content_type {
+            config["content_type"] = json!(content_type);
+        }
+        if let Some(ref secret) = self.secret {
+            config["secret"] = json!(secret.expose());
+        }
+        if let Some(insecure) = self.insecure_ssl {
+            config["insecure_ssl"] = json!(if insecure { "1" } else { "0" });
+        }
+        config
+    }
+
+    /// True if no config field is set
+    pub fn is_empty(&self) -> bool {
+        self.url.is_none() && self.content_type.is_none() && self.secret.is_none() && self.insecure_ssl.is_none()
+    }
+}
+
+/// Request parameters for create_webhook tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct CreateWebhookRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Scope
+    #[serde(flatten)]
+    pub scope: HookScope,
+
+    /// Delivery settings
+    #[serde(flatten)]
+    pub config: HookConfig,
+
+    /// Events that trigger the hook
+    #[schemars(description = "Events that trigger the hook, e.g. ['push', 'pull_request'] or ['*'] (default: ['push'])")]
+    pub events: Option<Vec<String>>,
+
+    /// Whether deliveries are sent
+    #[schemars(description = "Whether deliveries are sent (default: true)")]
+    pub active: Option<bool>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+/// Request parameters for update_webhook tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct UpdateWebhookRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Scope
+    #[serde(flatten)]
+    pub scope: HookScope,
+
+    /// Webhook ID
+    #[schemars(description = "Webhook ID")]
+    pub hook_id: u64,
+
+    /// Delivery settings to change
+    #[serde(flatten)]
+    pub config: HookConfig,
+
+    /// Events that trigger the hook
+    #[schemars(description = "Events that trigger the hook; replaces the current list")]
+    pub events: Option<Vec<String>>,
+
+    /// Whether deliveries are sent
+    #[schemars(description = "Whether deliveries are sent")]
+    pub active: Option<bool>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+/// Request parameters for get_webhook, delete_webhook and ping_webhook tools
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct WebhookRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Scope
+    #[serde(flatten)]
+    pub scope: HookScope,
+
+    /// Webhook ID
+    #[schemars(description = "Webhook ID")]
+    pub hook_id: u64,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+/// Request parameters for list_webhook_deliveries tool
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ListWebhookDeliveriesRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Scope
+    #[serde(flatten)]
+    pub scope: HookScope,
+
+    /// Webhook ID
+    #[schemars(description = "Webhook ID")]
+    pub hook_id: u64,
+
+    /// Only failed deliveries
+    #[schemars(description = "Only return failed deliveries (error status codes or no response) (default: false)")]
+    pub failed_only: Option<bool>,
+
+    /// Maximum number of deliveries to fetch
+    #[schemars(description = "Maximum number of recent deliveries to fetch, before filtering (default: 30, max: 100)")]
+    pub limit: Option<u32>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+/// Request parameters for get_webhook_delivery and redeliver_webhook tools
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct WebhookDeliveryRequest {
+    /// The account to use (e.g., 'home', 'work'). Uses default if not specified.
+    #[schemars(description = "The account to use (e.g., 'home', 'work'). Uses default if not specified.")]
+    pub account: Option<String>,
+
+    /// Scope
+    #[serde(flatten)]
+    pub scope: HookScope,
+
+    /// Webhook ID
+    #[schemars(description = "Webhook ID")]
+    pub hook_id: u64,
+
+    /// Delivery ID
+    #[schemars(description = "Delivery ID, from list_webhook_deliveries")]
+    pub delivery_id: u64,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+/// True if a delivery (from the deliveries API) failed: an error status or no response at all
+pub fn delivery_failed(delivery: &Value) -> bool {
+    delivery["status_code"].as_u64().is_none_or(|code| code == 0 || code >= 400)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hook_config_body() {
+        let json = r#"{"owner": "tatari-tv", "url": "https://ci.example.com/hook", "secret": "s3cret", "insecure_ssl": false}"#;
+        let request: CreateWebhookRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.scope.collection(), "orgs/tatari-tv/hooks");
+        assert!(!format!("{request:?}").contains("s3cret"));
+        assert_eq!(
+            request.config.to_body(),
+            json!({"url": "https://ci.example.com/hook", "secret": "s3cret", "insecure_ssl": "0"})
+        );
+        assert!(HookConfig::default().is_empty());
+    }
+
+    #[test]
+    fn test_delivery_failed() {
+        assert!(!delivery_failed(&json!({"status_code": 200})));
+        assert!(delivery_failed(&json!({"status_code": 502})));
+        assert!(delivery_failed(&json!({"status_code": 0})));
+        assert!(delivery_failed(&json!({})));
+    }
+}
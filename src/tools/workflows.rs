@@ -1,5 +1,6 @@
 //! Workflow and artifact tool request types
 
+use crate::output::OutputOptions;
 use rmcp::schemars;
 use schemars::JsonSchema;
 use serde::Deserialize;
@@ -34,6 +35,10 @@ pub struct ListWorkflowRunsRequest {
     /// Maximum number of runs to return
     #[schemars(description = "Maximum number of runs to return (default: 20)")]
     pub limit: Option<u32>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
 }
 
 /// Request parameters for list_run_artifacts tool
@@ -54,6 +59,10 @@ pub struct ListRunArtifactsRequest {
     /// Workflow run ID
     #[schemars(description = "Workflow run ID")]
     pub run_id: u64,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
 }
 
 /// Request parameters for download_run_artifact tool
@@ -82,6 +91,10 @@ pub struct DownloadRunArtifactRequest {
     /// Directory to download to
     #[schemars(description = "Directory to download artifacts to (default: current directory)")]
     pub dir: Option<String>,
+
+    /// Output shaping options (field selection, jq filter)
+    #[serde(flatten)]
+    pub output: OutputOptions,
 }
 
 #[cfg(test)]